@@ -5,6 +5,7 @@ pub mod wrapped;
 
 pub use unwrapped::{Opts, UnwrappedFieldProcOpts, UnwrappedProcUsageOpts, unwrapped};
 pub use utils::{
-    CommonOpts, FieldProcOpts as CommonFieldProcOpts, ProcUsageOpts as CommonProcUsageOpts,
+    CommonOpts, FieldContext, FieldProcOpts as CommonFieldProcOpts,
+    ProcUsageOpts as CommonProcUsageOpts,
 };
 pub use wrapped::{FieldProcOpts, WrappedOpts, WrappedProcUsageOpts, wrapped};
@@ -2,42 +2,303 @@ use std::collections::HashMap;
 
 use bon::Builder;
 use darling::{FromDeriveInput, FromField};
-use quote::{format_ident, quote};
-use syn::DeriveInput;
-
-use crate::utils::{
-    CommonOpts, FieldProcOpts, ProcUsageOpts, bon_builder_info, build_derive_output,
-    collect_field_attrs, generic_args, get_struct_data, raw_ident_name, snake_to_pascal_ident,
-    unique_state_ident,
+use quote::{format_ident, quote, quote_spanned};
+use syn::{DeriveInput, spanned::Spanned as _};
+
+use crate::{
+    utils::{
+        CommonOpts, FieldContext, FieldProcOpts, ProcUsageOpts, bon_builder_info,
+        build_derive_output, cfg_attrs, check_field_names, collect_field_attrs, derives_serde,
+        doc_attrs, field_count, field_key, field_member, field_position, float_derive_warnings,
+        forwarded_container_attrs, forwarded_test_attrs, gated_fields, generic_args,
+        get_struct_data, has_serde_flag, is_boxed_option_type, is_dyn_any_pointer,
+        is_known_default_type, is_option_type, is_string_type, is_vec_type, lib_path,
+        mirror_generics, missing_error, parse_attr_list, parse_derive_list, parse_field_default,
+        parse_field_type, parse_name_list, raw_ident_name, serde_default_fn, snake_to_pascal_ident,
+        unique_state_ident, unwrapped_error, with_where_predicates,
+    },
+    wrapped::{WrappedFieldOpts, WrappedOpts},
 };
 
 #[derive(Clone, Debug, Default, FromField)]
 #[darling(default, attributes(unwrapped))]
 struct FieldOpts {
     skip: bool,
+
+    /// Attributes to add to this field on the generated struct
+    #[darling(rename = "attr", with = parse_attr_list)]
+    extra_attrs: Vec<proc_macro2::TokenStream>,
+
+    /// Tag variants for which this field is required, see `Opts::tag`
+    #[darling(multiple)]
+    when_tag: Vec<syn::Ident>,
+
+    /// Value of this field in the generated `Default` impl, see `Opts::auto_default`, and its
+    /// fallback for `None` or when skipped, see `Opts::default_from` and `Opts::skip_default`
+    ///
+    /// A bare `default` means `Default::default()`. A skipped field with a default no longer needs
+    /// an `into_original` parameter.
+    #[darling(with = parse_field_default)]
+    default: Option<syn::Expr>,
+
+    /// Function called with no arguments in place of `default`, e.g. `default_fn = "Vec::new"`
+    default_fn: Option<syn::Path>,
+
+    /// Predicate over the converted field, bound as `value`, that `try_from` must satisfy
+    assert: Option<syn::Expr>,
+
+    /// Unwrap an `Option<T>` field into `T`'s own unwrapped struct, overriding `Opts::recursive`
+    recursive: Option<bool>,
+
+    /// Name of this field on the generated struct, every option still refers to the original name
+    rename: Option<syn::Ident>,
+
+    /// Value of this field in the generated `sample()`, overriding `default`, see `Opts::sample`
+    sample: Option<syn::Expr>,
+
+    /// Leave this field out of zeroizing with `#[zeroize(skip)]`, see `Opts::zeroize`
+    zeroize_skip: bool,
+
+    /// Generate `<field>_as::<T>()` downcasting a `Box<dyn Any>` field, see `downcast_helper`
+    downcast: bool,
+
+    /// Function converting this field in `try_from`, `fn(FieldTy) -> Result<T, E>` with `T` given
+    /// by `ty`. Any `Err` becomes an `UnwrappedError` for the field, like an `Option`-like wrapper.
+    with: Option<syn::Path>,
+
+    /// Type of a `with` field on the generated struct
+    #[darling(rename = "ty", with = parse_field_type)]
+    with_ty: Option<syn::Type>,
+
+    /// Function converting a `with` field back, `fn(T) -> FieldTy`, `From<T>` by default
+    back: Option<syn::Path>,
 }
 
 #[derive(Builder, Clone, Debug, FromDeriveInput)]
-#[darling(attributes(unwrapped), supports(struct_any))]
+#[darling(attributes(unwrapped), supports(struct_any, enum_any))]
 pub struct Opts {
     name: Option<syn::Ident>,
     prefix: Option<syn::Ident>,
     suffix: Option<syn::Ident>,
 
+    /// Name of the generated inherent conversion method, `try_from` by default
+    method_name: Option<syn::Ident>,
+
+    /// Path to the `unwrapped` crate, overriding the proc usage `lib_holder_name`
+    #[darling(rename = "crate")]
+    krate: Option<syn::Path>,
+
     /// Custom derives to add to the generated struct (in addition to Clone, Debug, Default)
     #[builder(default)]
-    #[darling(skip)]
+    #[darling(default, rename = "derive", with = parse_derive_list)]
     struct_derives: Vec<proc_macro2::TokenStream>,
 
     /// Custom attributes to add to the generated struct
     #[builder(default)]
-    #[darling(skip)]
+    #[darling(default, rename = "attr", with = parse_attr_list)]
     struct_attrs: Vec<proc_macro2::TokenStream>,
 
     /// Per-field attributes to add to specific fields
     #[builder(default)]
     #[darling(skip)]
     field_attrs: HashMap<String, Vec<proc_macro2::TokenStream>>,
+
+    /// Extra `where` predicates for the generated struct and every generated impl
+    #[builder(default)]
+    #[darling(skip)]
+    where_predicates: Vec<syn::WherePredicate>,
+
+    /// Enum-typed field whose runtime value decides which `when_tag` fields are required
+    tag: Option<syn::Ident>,
+
+    /// Derive `proptest_derive::Arbitrary` on the generated struct (requires the `proptest` feature)
+    #[builder(default)]
+    #[darling(default)]
+    arbitrary: bool,
+
+    /// Derive `zeroize::Zeroize` on the generated struct (requires the `zeroize` feature)
+    #[builder(default)]
+    #[darling(default)]
+    zeroize: bool,
+
+    /// Generate `for_each_string_field`, visiting every `String` field of the generated struct
+    #[builder(default)]
+    #[darling(default)]
+    string_visitor: bool,
+
+    /// Generate `{Original}View<'_>` and `view`, borrowing the original instead of moving it
+    #[builder(default)]
+    #[darling(default)]
+    view: bool,
+
+    /// Generate a `#[track_caller]` `expect_unwrapped`, panicking at the call site on `None`
+    #[builder(default)]
+    #[darling(default)]
+    expect: bool,
+
+    /// Generate `try_split`, converting a clone of the original and returning both
+    #[builder(default)]
+    #[darling(default)]
+    split: bool,
+
+    /// Generate `try_as_unwrapped(&self)` on the original, cloning fields instead of consuming it
+    #[builder(default)]
+    #[darling(default)]
+    borrow: bool,
+
+    /// Give the generated struct the visibility of the input struct instead of `pub`
+    #[builder(default)]
+    #[darling(default)]
+    inherit_vis: bool,
+
+    /// Make every generated field `pub` instead of mirroring the visibility of the input field
+    #[builder(default)]
+    #[darling(default)]
+    all_pub: bool,
+
+    /// Emit `type {Uw} = {Original};` instead of a struct when unwrapping would change no field
+    #[builder(default)]
+    #[darling(default)]
+    transparent: bool,
+
+    /// Don't copy the input fields' doc comments onto the generated fields
+    #[builder(default)]
+    #[darling(default)]
+    no_docs: bool,
+
+    /// Attribute namespaces copied verbatim from the input struct and its fields, e.g. `diesel`
+    #[builder(default)]
+    #[darling(default, with = parse_name_list)]
+    forward_attrs: Vec<syn::LitStr>,
+
+    /// Attribute namespaces never copied from the input, overriding `forward_attrs` and defaults
+    #[builder(default)]
+    #[darling(default, with = parse_name_list)]
+    skip_attrs: Vec<syn::LitStr>,
+
+    /// Generate `From<Unwrapped> for Original` even with skipped fields, filling them with their
+    /// `default` or `Default::default()`
+    #[builder(default)]
+    #[darling(default)]
+    skip_default: bool,
+
+    /// Generate `From<Original> for Unwrapped`, filling `None` fields with their `default` or
+    /// `Default::default()` instead of failing
+    #[builder(default)]
+    #[darling(default)]
+    default_from: bool,
+
+    /// Generate `into_original_or(fallback)`, cloning skipped fields from `fallback`
+    #[builder(default)]
+    #[darling(default)]
+    into_original_or: bool,
+
+    /// Allowlist of fields kept on the generated struct, every other field is skipped
+    #[builder(default)]
+    #[darling(default, with = parse_name_list)]
+    only: Vec<syn::LitStr>,
+
+    /// Generate `analyze`, reporting the presence of every field without converting
+    #[builder(default)]
+    #[darling(default)]
+    analyze: bool,
+
+    /// Generate `missing_fields`, listing the fields that would fail `try_from` for being `None`
+    #[builder(default)]
+    #[darling(default)]
+    missing_fields: bool,
+
+    /// Implement `Default` for the generated struct, using the field-level `default` where given
+    #[builder(default)]
+    #[darling(default)]
+    auto_default: bool,
+
+    /// Like `auto_default`, but reject fields whose type is not known to implement `Default`
+    /// and that have no field-level `default`
+    #[builder(default)]
+    #[darling(default)]
+    smart_default: bool,
+
+    /// Generate `try_from_counted`, also returning how many `Option` fields were populated
+    #[builder(default)]
+    #[darling(default)]
+    counted: bool,
+
+    /// Generate `from_into`, converting from anything that implements `Into<Original>`
+    #[builder(default)]
+    #[darling(default)]
+    from_into: bool,
+
+    /// Generate `try_from_boxed`, consuming a boxed original
+    #[builder(default)]
+    #[darling(default)]
+    boxed_ctor: bool,
+
+    /// Generate `try_from_partial`, returning every error alongside the struct when there are none
+    #[builder(default)]
+    #[darling(default)]
+    collect_errors: bool,
+
+    /// Unwrap every `Option<T>` field into `T`'s own unwrapped struct, `T` must derive `Unwrapped`
+    #[builder(default)]
+    #[darling(default)]
+    recursive: bool,
+
+    /// Check in debug builds that `try_from` carried every field over unchanged
+    #[builder(default)]
+    #[darling(default)]
+    debug_checks: bool,
+
+    /// Function `fn(&'static str, bool)` that `try_from` reports each field's presence to
+    metrics: Option<syn::Path>,
+
+    /// Generate `as_original`, reinterpreting a reference when both structs share a `#[repr(C)]` layout
+    #[builder(default)]
+    #[darling(default)]
+    reinterpret: bool,
+
+    /// Name of the bon state module of the generated struct, when it derives `Builder`
+    builder_state_mod: Option<syn::Ident>,
+
+    /// Reject field combinations that compile but are likely mistakes
+    #[builder(default)]
+    #[darling(default)]
+    strict: bool,
+
+    /// Generate `__bench_try_from`, a non-inlined conversion for benchmarks (requires the `bench` feature)
+    #[builder(default)]
+    #[darling(default)]
+    bench: bool,
+
+    /// In debug builds, name the failing conversion in `UnwrappedError::source_field`
+    #[builder(default)]
+    #[darling(default)]
+    provenance: bool,
+
+    /// Generate `to_debug_map`, formatting every field with `Debug` keyed by its name
+    #[builder(default)]
+    #[darling(default)]
+    debug_map: bool,
+
+    /// Generate `presence_mask`, a bitmask of the populated fields in declaration order
+    #[builder(default)]
+    #[darling(default)]
+    presence_mask: bool,
+
+    /// Convert directly between this struct and the `#[derive(Wrapped)]` mirror of the same input
+    #[builder(default)]
+    #[darling(default)]
+    bridge: bool,
+
+    /// Generate `builder()`, a plain builder for the generated struct that does not need bon
+    #[builder(default)]
+    #[darling(default)]
+    builder: bool,
+
+    /// Generate `sample()`, a deterministic fixture (only with the `testing` feature)
+    #[builder(default)]
+    #[darling(default)]
+    sample: bool,
 }
 
 impl Opts {
@@ -63,6 +324,14 @@ impl Opts {
         self
     }
 
+    /// Add a `where` predicate to the generated struct and every generated impl, e.g.
+    /// `quote!(T: Serialize)` for a derive whose bounds differ from the input struct's
+    pub fn with_where(mut self, tokens: impl Into<proc_macro2::TokenStream>) -> Self {
+        self.where_predicates
+            .push(syn::parse2(tokens.into()).expect("Expected a where predicate"));
+        self
+    }
+
     /// Add an attribute to a specific field by name
     pub fn with_field_attr(
         mut self,
@@ -77,6 +346,50 @@ impl Opts {
         self
     }
 
+    /// Visibility of a field on the generated structs
+    fn field_vis(&self, f: &syn::Field) -> syn::Visibility {
+        if self.all_pub {
+            syn::parse_quote!(pub)
+        } else {
+            f.vis.clone()
+        }
+    }
+
+    /// Parse a field's options, skipping it when it is left out of the `only` allowlist
+    fn field_opts(&self, s: &syn::DataStruct, f: &syn::Field) -> FieldOpts {
+        let mut field_opts = FieldOpts::from_field(f).expect("Wrong field options");
+        if !self.only.is_empty() {
+            let name = field_key(s, f);
+            field_opts.skip |= !self.only.iter().any(|allowed| allowed.value() == name);
+        }
+        field_opts.recursive.get_or_insert(self.recursive);
+        // Setting both is rejected by `unwrapped`, so `default_fn` only fills a missing `default`
+        if let Some(default_fn) = field_opts.default_fn.take() {
+            field_opts
+                .default
+                .get_or_insert_with(|| syn::parse_quote!(#default_fn()));
+        }
+        field_opts
+    }
+
+    /// How a field is accessed on the generated struct, named fields may be renamed and tuple
+    /// fields are renumbered around skipped ones
+    fn generated_member(&self, s: &syn::DataStruct, f: &syn::Field) -> syn::Member {
+        match &f.ident {
+            Some(ident) => {
+                syn::Member::Named(self.field_opts(s, f).rename.unwrap_or(ident.clone()))
+            },
+            None => syn::Member::Unnamed(
+                s.fields
+                    .iter()
+                    .take_while(|other| !std::ptr::eq(*other, f))
+                    .filter(|other| !self.field_opts(s, other).skip)
+                    .count()
+                    .into(),
+            ),
+        }
+    }
+
     fn to_common(&self) -> CommonOpts {
         CommonOpts {
             name: self.name.clone(),
@@ -85,6 +398,8 @@ impl Opts {
             struct_derives: self.struct_derives.clone(),
             struct_attrs: self.struct_attrs.clone(),
             field_attrs: self.field_attrs.clone(),
+            forward_attrs: self.forward_attrs.iter().map(syn::LitStr::value).collect(),
+            skip_attrs: self.skip_attrs.iter().map(syn::LitStr::value).collect(),
         }
     }
 }
@@ -114,32 +429,51 @@ impl UnwrappedFieldProcOpts {
 #[derive(Clone, Debug, Default)]
 pub struct UnwrappedProcUsageOpts {
     pub fields_to_unwrap: HashMap<String, bool>,
-    lib_holder_name: Option<syn::Ident>,
+    lib_holder_name: Option<syn::Path>,
     /// Field transformations: name -> (should_unwrap, attributes)
     pub field_opts: HashMap<String, UnwrappedFieldProcOpts>,
     /// Dynamic field attribute generator
     pub field_attr_fn: Option<fn(&syn::Field) -> Option<proc_macro2::TokenStream>>,
+    /// Dynamic field attribute generator that also sees the field's position and struct
+    pub field_attr_fn_ctx: Option<fn(&FieldContext<'_>) -> Option<proc_macro2::TokenStream>>,
+    /// Custom `Option`-like wrappers: type name -> extraction method
+    pub option_like: Vec<(syn::Ident, syn::Ident)>,
 }
 
 impl UnwrappedProcUsageOpts {
     pub fn new(
         fields_to_unwrap: HashMap<String, bool>,
-        lib_holder_name: Option<syn::Ident>,
+        lib_holder_name: Option<syn::Path>,
     ) -> Self {
         Self {
             fields_to_unwrap,
             lib_holder_name,
             field_opts: HashMap::new(),
             field_attr_fn: None,
+            field_attr_fn_ctx: None,
+            option_like: Vec::new(),
         }
     }
 
     pub fn lib_path(&self) -> syn::Path {
-        if let Some(name) = &self.lib_holder_name {
-            syn::parse_str(&format!("{}::unwrapped", name)).unwrap()
-        } else {
-            syn::parse_str("unwrapped").unwrap()
-        }
+        lib_path(self.lib_holder_name.as_ref())
+    }
+
+    /// Set the crate that re-exports `unwrapped`, or the full path to it, see `lib_path`
+    pub fn with_lib_holder(mut self, name: impl Into<syn::Path>) -> Self {
+        self.lib_holder_name = Some(name.into());
+        self
+    }
+
+    /// Treat fields of type `Wrapper<T>` like `Option<T>`, matching on the last path segment.
+    ///
+    /// `extract_method` must take the wrapper by value and return `Result<T, E>`, where `E` converts
+    /// into `Box<dyn Error + Send + Sync>`. Any `Err` becomes an `UnwrappedError` for the field
+    /// carrying `E` as `UnwrappedErrorKind::Conversion`. Converting back uses `From<T>` for the
+    /// wrapper.
+    pub fn with_option_like(mut self, ident: syn::Ident, extract_method: syn::Ident) -> Self {
+        self.option_like.push((ident, extract_method));
+        self
     }
 
     /// Set options for a specific field
@@ -153,6 +487,18 @@ impl UnwrappedProcUsageOpts {
         self
     }
 
+    /// Set options for many fields at once, later entries override earlier ones
+    pub fn with_field_opts_bulk<K: AsRef<str>>(
+        mut self,
+        field_opts: impl IntoIterator<Item = (K, UnwrappedFieldProcOpts)>,
+    ) -> Self {
+        for (field_name, opts) in field_opts {
+            self.field_opts
+                .insert(field_name.as_ref().to_string(), opts);
+        }
+        self
+    }
+
     /// Set a dynamic field attribute generator
     pub fn with_field_attr_fn(
         mut self,
@@ -162,6 +508,15 @@ impl UnwrappedProcUsageOpts {
         self
     }
 
+    /// Set a dynamic field attribute generator that also sees the field's position and struct
+    pub fn with_field_attr_fn_ctx(
+        mut self,
+        f: fn(&FieldContext<'_>) -> Option<proc_macro2::TokenStream>,
+    ) -> Self {
+        self.field_attr_fn_ctx = Some(f);
+        self
+    }
+
     fn to_common(&self) -> ProcUsageOpts {
         let mut field_opts = HashMap::new();
         for (name, opts) in &self.field_opts {
@@ -178,158 +533,2274 @@ impl UnwrappedProcUsageOpts {
             lib_holder_name: self.lib_holder_name.clone(),
             field_opts,
             field_attr_fn: self.field_attr_fn,
+            field_attr_fn_ctx: self.field_attr_fn_ctx,
         }
     }
 }
 
-pub fn unwrapped(
-    input: &DeriveInput,
-    options: Option<Opts>,
-    proc_usage_opts: UnwrappedProcUsageOpts,
-) -> proc_macro2::TokenStream {
-    let opts = options.unwrap_or_else(|| Opts::from_derive_input(input).expect("Wrong options"));
-    let lib_path = proc_usage_opts.lib_path();
-    let common_opts = opts.to_common();
-    let common_proc_opts = proc_usage_opts.to_common();
-
-    let original_ident = &input.ident;
-    let unwrapped_ident = &opts.unwrapped_ident(original_ident);
+/// Whether the input struct is `#[repr(C)]`, possibly alongside other repr hints
+fn has_repr_c(attrs: &[syn::Attribute]) -> bool {
+    attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("repr"))
+        .filter_map(|attr| attr.meta.require_list().ok())
+        .any(|list| {
+            list.tokens
+                .clone()
+                .into_iter()
+                .any(|token| matches!(token, proc_macro2::TokenTree::Ident(ident) if ident == "C"))
+        })
+}
 
-    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
-    let s = get_struct_data(input);
+/// A field converted by a fallible extraction instead of `ok_or`
+struct OptionLike<'a> {
+    /// Type of the field on the generated struct
+    inner_ty: &'a syn::Type,
+    extract: Extract<'a>,
+}
 
-    // Check if any field has skip attribute
-    let has_skipped_fields = s.fields.iter().any(|f| {
-        let field_opts = FieldOpts::from_field(f).expect("Wrong field options");
-        field_opts.skip
-    });
+enum Extract<'a> {
+    /// Method of a registered wrapper type, see `UnwrappedProcUsageOpts::with_option_like`
+    Method(&'a syn::Ident),
+    /// The field's own `with` function and optional `back` function
+    With(&'a syn::Path, Option<&'a syn::Path>),
+}
 
-    let fields = s.fields.iter().filter_map(|f| {
-        let field_opts = FieldOpts::from_field(f).expect("Wrong field options");
+impl OptionLike<'_> {
+    /// The `Result` of extracting `value`
+    fn extract(&self, value: proc_macro2::TokenStream) -> proc_macro2::TokenStream {
+        match self.extract {
+            Extract::Method(method) => quote! { #value.#method() },
+            Extract::With(with, _) => quote! { #with(#value) },
+        }
+    }
 
-        // Skip this field entirely if skip attribute is present
-        if field_opts.skip {
-            return None;
+    /// `back` applied to `value`, if the field has one
+    fn back(&self, value: &proc_macro2::TokenStream) -> Option<proc_macro2::TokenStream> {
+        match self.extract {
+            Extract::With(_, Some(back)) => Some(quote! { #back(#value) }),
+            _ => None,
         }
+    }
 
-        let name = &f.ident;
-        let ty = &f.ty;
-        let name_str = name.as_ref().unwrap().to_string();
+    /// The original field converted back from `value`
+    fn convert_back(&self, value: proc_macro2::TokenStream) -> proc_macro2::TokenStream {
+        self.back(&value)
+            .unwrap_or_else(|| quote! { ::core::convert::From::from(#value) })
+    }
+}
 
-        // Collect field attributes
-        let field_attrs = collect_field_attrs(f, &common_opts, &common_proc_opts);
-
-        if let syn::Type::Path(p) = ty
-            && let Some(seg) = p.path.segments.last()
-            && seg.ident == "Option"
-            && *proc_usage_opts
-                .fields_to_unwrap
-                .get(&name_str)
-                .unwrap_or(&true)
-            && let syn::PathArguments::AngleBracketed(args) = &seg.arguments
-            && let Some(syn::GenericArgument::Type(inner_ty)) = args.args.first()
-        {
-            return Some(quote! { #(#field_attrs)* pub #name: #inner_ty });
-        }
-        Some(quote! { #(#field_attrs)* pub #name: #ty })
-    });
+/// A field with a `with` function, or whose type is a registered `Option`-like wrapper
+fn option_like_field<'a>(
+    s: &syn::DataStruct,
+    f: &'a syn::Field,
+    field_opts: &'a FieldOpts,
+    proc_usage_opts: &'a UnwrappedProcUsageOpts,
+) -> Option<OptionLike<'a>> {
+    if let (Some(with), Some(ty)) = (&field_opts.with, &field_opts.with_ty) {
+        return Some(OptionLike {
+            inner_ty: ty,
+            extract: Extract::With(with, field_opts.back.as_ref()),
+        });
+    }
 
-    let from_fields = s.fields.iter().filter_map(|f| {
-        let field_opts = FieldOpts::from_field(f).expect("Wrong field options");
+    let name_str = field_key(s, f);
+    if !*proc_usage_opts
+        .fields_to_unwrap
+        .get(&name_str)
+        .unwrap_or(&true)
+    {
+        return None;
+    }
 
-        // Skip this field if skip attribute is present
-        if field_opts.skip {
-            return None;
-        }
+    let syn::Type::Path(p) = &f.ty else {
+        return None;
+    };
+    let seg = p.path.segments.last()?;
+    let (_, extract_method) = proc_usage_opts
+        .option_like
+        .iter()
+        .find(|(ident, _)| seg.ident == *ident)?;
+    let syn::PathArguments::AngleBracketed(args) = &seg.arguments else {
+        return None;
+    };
+    match args.args.first()? {
+        syn::GenericArgument::Type(inner_ty) => Some(OptionLike {
+            inner_ty,
+            extract: Extract::Method(extract_method),
+        }),
+        _ => None,
+    }
+}
 
-        let name = &f.ident;
-        let ty = &f.ty;
-        let name_str = name.as_ref().unwrap().to_string();
-
-        if let syn::Type::Path(p) = ty
-            && let Some(seg) = p.path.segments.last()
-            && seg.ident == "Option"
-            && *proc_usage_opts
-                .fields_to_unwrap
-                .get(&name_str)
-                .unwrap_or(&true)
-        {
-            return Some(quote! { #name: Some(from.#name) });
-        }
-        Some(quote! { #name: from.#name })
-    });
+/// Parameter of `into_original` for a skipped field, tuple fields are named after their position
+fn skipped_param(s: &syn::DataStruct, f: &syn::Field) -> syn::Ident {
+    match &f.ident {
+        Some(ident) => ident.clone(),
+        None => format_ident!("field_{}", field_key(s, f)),
+    }
+}
+
+/// Whether an `Option<T>` field becomes `T` in the generated struct
+fn unwraps_field(
+    s: &syn::DataStruct,
+    f: &syn::Field,
+    field_opts: &FieldOpts,
+    proc_usage_opts: &UnwrappedProcUsageOpts,
+) -> bool {
+    let name_str = field_key(s, f);
+    is_option_type(&f.ty).is_some()
+        && field_opts.when_tag.is_empty()
+        && field_opts.with.is_none()
+        && *proc_usage_opts
+            .fields_to_unwrap
+            .get(&name_str)
+            .unwrap_or(&true)
+}
+
+/// The `T` of an unwrapped `Option<T>` field that becomes `T`'s own unwrapped struct
+fn recursive_field<'a>(
+    s: &syn::DataStruct,
+    f: &'a syn::Field,
+    field_opts: &FieldOpts,
+    proc_usage_opts: &UnwrappedProcUsageOpts,
+) -> Option<&'a syn::Type> {
+    if field_opts.recursive != Some(true) || !unwraps_field(s, f, field_opts, proc_usage_opts) {
+        return None;
+    }
+    is_option_type(&f.ty)
+}
+
+/// The `T` of an unwrapped `Box<Option<T>>` field, which becomes `Box<T>`
+fn boxed_option_field<'a>(
+    s: &syn::DataStruct,
+    f: &'a syn::Field,
+    field_opts: &FieldOpts,
+    proc_usage_opts: &UnwrappedProcUsageOpts,
+) -> Option<&'a syn::Type> {
+    let inner_ty = is_boxed_option_type(&f.ty)?;
+    let unwrap = field_opts.when_tag.is_empty()
+        && field_opts.with.is_none()
+        && *proc_usage_opts
+            .fields_to_unwrap
+            .get(&field_key(s, f))
+            .unwrap_or(&true);
+    unwrap.then_some(inner_ty)
+}
+
+/// Build the `try_from` presence checks for `when_tag` fields, driven by the value of the tag field
+fn tag_checks(
+    s: &syn::DataStruct,
+    tag: Option<&syn::Ident>,
+    lib_path: &syn::Path,
+    source: &proc_macro2::TokenStream,
+) -> syn::Result<Vec<proc_macro2::TokenStream>> {
+    let checks = tag_conditions(s, tag, &quote! { from })?
+        .into_iter()
+        .map(|(f, required)| {
+            let name = field_member(s, f);
+            let field_name_str = field_key(s, f);
+            let cfgs = cfg_attrs(&f.attrs);
+            let missing = missing_error(lib_path, &field_name_str, source);
+            quote! {
+                #(#cfgs)*
+                if #required && from.#name.is_none() {
+                    return Err(#missing);
+                }
+            }
+        })
+        .collect();
+
+    Ok(checks)
+}
 
-    let try_from_fields = s.fields.iter().filter_map(|f| {
+/// Every `when_tag` field with the condition under which it is required, e.g.
+/// `matches!(from.kind, Kind::Card { .. })` for a `receiver` of `from`
+fn tag_conditions<'a>(
+    s: &'a syn::DataStruct,
+    tag: Option<&syn::Ident>,
+    receiver: &proc_macro2::TokenStream,
+) -> syn::Result<Vec<(&'a syn::Field, proc_macro2::TokenStream)>> {
+    let tag_path = match tag {
+        Some(tag) => {
+            let tag_field = s
+                .fields
+                .iter()
+                .find(|f| f.ident.as_ref() == Some(tag))
+                .ok_or_else(|| syn::Error::new_spanned(tag, format!("no field named `{}`", tag)))?;
+            let syn::Type::Path(tag_ty) = &tag_field.ty else {
+                return Err(syn::Error::new_spanned(
+                    &tag_field.ty,
+                    "the tag field must have an enum type",
+                ));
+            };
+            // Drop generic arguments, they are not allowed in a pattern path
+            let leading_colon = &tag_ty.path.leading_colon;
+            let segments = tag_ty.path.segments.iter().map(|seg| &seg.ident);
+            Some(quote! { #leading_colon #(#segments)::* })
+        },
+        None => None,
+    };
+
+    let mut conditions = Vec::new();
+    for f in s.fields.iter() {
         let field_opts = FieldOpts::from_field(f).expect("Wrong field options");
+        if field_opts.when_tag.is_empty() {
+            continue;
+        }
 
-        // Skip this field if skip attribute is present
-        if field_opts.skip {
-            return None;
+        let (Some(tag), Some(tag_path)) = (tag, &tag_path) else {
+            return Err(syn::Error::new_spanned(
+                f,
+                "`when_tag` requires a struct-level `#[unwrapped(tag = ...)]`",
+            ));
+        };
+        if is_option_type(&f.ty).is_none() {
+            return Err(syn::Error::new_spanned(
+                &f.ty,
+                "`when_tag` can only be used on `Option<T>` fields",
+            ));
         }
 
+        let variants = field_opts
+            .when_tag
+            .iter()
+            .map(|variant| quote! { #tag_path::#variant { .. } });
+        conditions.push((f, quote! { matches!(#receiver.#tag, #(#variants)|*) }));
+    }
+
+    Ok(conditions)
+}
+
+/// The `source_field` of errors raised by the generated `method`.
+///
+/// With `provenance`, debug builds name the conversion that observed the `None`, while release
+/// builds and conversions without `provenance` leave it empty.
+fn error_source(
+    opts: &Opts,
+    unwrapped_ident: &syn::Ident,
+    method: &str,
+) -> proc_macro2::TokenStream {
+    if opts.provenance {
+        let label = format!("{}::{}", unwrapped_ident, method);
+        quote! { if cfg!(debug_assertions) { #label } else { "" } }
+    } else {
+        quote! { "" }
+    }
+}
+
+/// Build `TryFrom<Wrapped> for Unwrapped` and `From<Unwrapped> for Wrapped`.
+///
+/// The wrapped mirror is assumed to come from `#[derive(Wrapped)]`, so every non-`Option` field of
+/// the input is `Option<T>` there. Fields skipped on the unwrapped side become `None` on the wrapped
+/// side, while fields skipped only on the wrapped side cannot be recovered and are rejected.
+fn bridge_impls(
+    input: &DeriveInput,
+    opts: &Opts,
+    unwrapped_ident: &syn::Ident,
+    lib_path: &syn::Path,
+    proc_usage_opts: &UnwrappedProcUsageOpts,
+) -> syn::Result<proc_macro2::TokenStream> {
+    let wrapped_opts = WrappedOpts::from_derive_input(input).expect("Wrong options");
+    if wrapped_opts.tristate {
+        return Err(syn::Error::new_spanned(
+            &input.ident,
+            "`bridge` does not support `#[wrapped(tristate)]`",
+        ));
+    }
+    let wrapped_ident = wrapped_opts.wrapped_ident(&input.ident);
+    let (impl_generics, _, where_clause) = input.generics.split_for_impl();
+    let s = get_struct_data(input);
+
+    let unwrapped_generics = mirror_generics(
+        &input.generics,
+        s.fields.iter().filter_map(|f| {
+            let field_opts = opts.field_opts(s, f);
+            (!field_opts.skip).then_some(&f.ty)
+        }),
+    );
+    let (_, mirror_ty_generics, _) = unwrapped_generics.split_for_impl();
+    let wrapped_generics = mirror_generics(
+        &input.generics,
+        s.fields.iter().filter_map(|f| {
+            let field_opts = WrappedFieldOpts::from_field(f).expect("Wrong field options");
+            (!field_opts.skip).then_some(&f.ty)
+        }),
+    );
+    let (_, wrapped_ty_generics, _) = wrapped_generics.split_for_impl();
+
+    let source = error_source(
+        opts,
+        unwrapped_ident,
+        &format!("try_from({})", wrapped_ident),
+    );
+    let reconcile_source = error_source(opts, unwrapped_ident, "reconcile");
+    let reconcile_tag_checks = tag_checks(s, opts.tag.as_ref(), lib_path, &reconcile_source)?;
+    let tag_checks = tag_checks(s, opts.tag.as_ref(), lib_path, &source)?;
+
+    let mut try_from_fields = Vec::new();
+    let mut from_fields = Vec::new();
+    let mut reconcile_fields = Vec::new();
+    for f in s.fields.iter() {
+        let field_opts = opts.field_opts(s, f);
+        let wrapped_field_opts = WrappedFieldOpts::from_field(f).expect("Wrong field options");
         let name = &f.ident;
-        let ty = &f.ty;
-        let name_str = name.as_ref().unwrap().to_string();
 
-        if let syn::Type::Path(p) = ty
-            && let Some(seg) = p.path.segments.last()
-            && seg.ident == "Option"
-            && *proc_usage_opts.fields_to_unwrap.get(&name_str).unwrap_or(&true)
-        {
-            let field_name_str = name.as_ref().unwrap().to_string();
-            return Some(quote! { #name: from.#name.ok_or(::#lib_path::UnwrappedError{ field_name: #field_name_str })? });
+        if !field_opts.skip && option_like_field(s, f, &field_opts, proc_usage_opts).is_some() {
+            return Err(syn::Error::new_spanned(
+                f,
+                "`bridge` does not support `Option`-like wrapper fields",
+            ));
+        }
+        if !field_opts.skip && boxed_option_field(s, f, &field_opts, proc_usage_opts).is_some() {
+            return Err(syn::Error::new_spanned(
+                f,
+                "`bridge` does not support `Box<Option<T>>` fields",
+            ));
+        }
+        if !field_opts.skip && recursive_field(s, f, &field_opts, proc_usage_opts).is_some() {
+            return Err(syn::Error::new_spanned(
+                f,
+                "`bridge` does not support `recursive` fields",
+            ));
+        }
+        if field_opts.rename.is_some() || wrapped_field_opts.rename.is_some() {
+            return Err(syn::Error::new_spanned(
+                f,
+                "`bridge` does not support renamed fields",
+            ));
         }
-        Some(quote! { #name: from.#name })
-    });
 
-    // Build struct-level attributes and derives
-    let struct_attrs = &opts.struct_attrs;
-    let derive_output = build_derive_output(&opts.struct_derives);
+        // Either presence is already tracked by an `Option` on both sides, or neither side wraps
+        let passthrough = if is_option_type(&f.ty).is_some() {
+            !unwraps_field(s, f, &field_opts, proc_usage_opts)
+        } else {
+            !wrapped_opts.is_nullable(f)
+        };
 
-    // Only generate From implementations if there are no skipped fields
-    if has_skipped_fields {
-        // Collect skipped fields for into_original method
-        let skipped_params = s.fields.iter().filter_map(|f| {
-            let field_opts = FieldOpts::from_field(f).expect("Wrong field options");
-            if field_opts.skip {
-                let name = &f.ident;
-                let ty = &f.ty;
-                Some(quote! { #name: #ty })
-            } else {
-                None
-            }
-        });
+        let cfgs = cfg_attrs(&f.attrs);
+        match (field_opts.skip, wrapped_field_opts.skip) {
+            (true, true) => {},
+            (true, false) => from_fields.push(quote! { #(#cfgs)* #name: None }),
+            (false, true) => {
+                return Err(syn::Error::new_spanned(
+                    f,
+                    "`bridge` cannot fill a field skipped only by `Wrapped`, skip it on both sides",
+                ));
+            },
+            (false, false) if passthrough => {
+                try_from_fields.push(quote! { #(#cfgs)* #name: from.#name });
+                from_fields.push(quote! { #(#cfgs)* #name: from.#name });
+                // An `Option` on both sides only overwrites when the patch holds a value
+                reconcile_fields.push(if is_option_type(&f.ty).is_some() {
+                    quote! { #(#cfgs)* if patch.#name.is_some() { self.#name = patch.#name; } }
+                } else {
+                    quote! { #(#cfgs)* { self.#name = patch.#name; } }
+                });
+            },
+            (false, false) => {
+                let missing = missing_error(lib_path, &name.as_ref().unwrap().to_string(), &source);
+                try_from_fields.push(quote! {
+                    #(#cfgs)*
+                    #name: from.#name.ok_or(#missing)?
+                });
+                from_fields.push(quote! { #(#cfgs)* #name: Some(from.#name) });
+                reconcile_fields.push(quote! {
+                    #(#cfgs)*
+                    if let Some(value) = patch.#name {
+                        self.#name = value;
+                    }
+                });
+            },
+        }
+    }
 
-        // Build field assignments for into_original
-        let into_original_fields = s.fields.iter().map(|f| {
-            let field_opts = FieldOpts::from_field(f).expect("Wrong field options");
-            let name = &f.ident;
-            let ty = &f.ty;
-            let name_str = name.as_ref().unwrap().to_string();
+    Ok(quote! {
+        impl #impl_generics ::core::convert::TryFrom<#wrapped_ident #wrapped_ty_generics> for #unwrapped_ident #mirror_ty_generics #where_clause {
+            type Error = #lib_path::UnwrappedError;
 
-            if field_opts.skip {
-                // Skipped fields come from parameters
-                quote! { #name }
-            } else if let syn::Type::Path(p) = ty
-                && let Some(seg) = p.path.segments.last()
-                && seg.ident == "Option"
-                && *proc_usage_opts
-                    .fields_to_unwrap
-                    .get(&name_str)
-                    .unwrap_or(&true)
-            {
-                // Non-skipped Option fields that were unwrapped -> wrap them back
-                quote! { #name: Some(self.#name) }
-            } else {
-                // Non-skipped non-Option fields
-                quote! { #name: self.#name }
-            }
-        });
+            fn try_from(from: #wrapped_ident #wrapped_ty_generics) -> ::core::result::Result<Self, Self::Error> {
+                let unwrapped = Self {
+                    #(#try_from_fields),*
+                };
 
-        let builder_helper = if let Some(builder_info) = bon_builder_info(input) {
-            let builder_ident = &builder_info.builder_ident;
-            let state_mod_ident = &builder_info.state_mod_ident;
-            let state_ident = unique_state_ident(&input.generics);
+                {
+                    let from = &unwrapped;
+                    #(#tag_checks)*
+                }
+
+                Ok(unwrapped)
+            }
+        }
+
+        impl #impl_generics ::core::convert::From<#unwrapped_ident #mirror_ty_generics> for #wrapped_ident #wrapped_ty_generics #where_clause {
+            fn from(from: #unwrapped_ident #mirror_ty_generics) -> Self {
+                Self {
+                    #(#from_fields),*
+                }
+            }
+        }
+
+        impl #impl_generics #unwrapped_ident #mirror_ty_generics #where_clause {
+            /// Apply the fields `patch` holds over `self`.
+            ///
+            /// Fields that are `None` in `patch` keep their value, and fields the patch has but
+            /// `self` skips are ignored. Tag checks run on the result.
+            #[allow(unused_mut, unused_variables)]
+            pub fn reconcile(mut self, patch: #wrapped_ident #wrapped_ty_generics) -> ::core::result::Result<Self, #lib_path::UnwrappedError> {
+                #(#reconcile_fields)*
+
+                {
+                    let from = &self;
+                    #(#reconcile_tag_checks)*
+                }
+
+                Ok(self)
+            }
+        }
+    })
+}
+
+/// Generate the mirror enum, unwrapping the `Option<T>` fields of every variant.
+///
+/// Named, tuple and unit variants are supported. A `None` field fails `try_from` with a
+/// `field_name` of `Variant.field`, or `Variant.0` for tuple variants.
+fn unwrapped_enum(
+    input: &DeriveInput,
+    data: &syn::DataEnum,
+    opts: &Opts,
+    proc_usage_opts: &UnwrappedProcUsageOpts,
+    lib_path: &syn::Path,
+) -> proc_macro2::TokenStream {
+    let original_ident = &input.ident;
+
+    // These helpers are built around the fields of a single struct
+    for (enabled, option) in [
+        (opts.tag.is_some(), "tag"),
+        (opts.arbitrary, "arbitrary"),
+        (opts.zeroize, "zeroize"),
+        (opts.string_visitor, "string_visitor"),
+        (opts.view, "view"),
+        (opts.expect, "expect"),
+        (opts.split, "split"),
+        (opts.borrow, "borrow"),
+        (opts.all_pub, "all_pub"),
+        (opts.transparent, "transparent"),
+        (opts.skip_default, "skip_default"),
+        (opts.default_from, "default_from"),
+        (opts.into_original_or, "into_original_or"),
+        (!opts.only.is_empty(), "only"),
+        (opts.analyze, "analyze"),
+        (opts.missing_fields, "missing_fields"),
+        (opts.auto_default, "auto_default"),
+        (opts.smart_default, "smart_default"),
+        (opts.counted, "counted"),
+        (opts.from_into, "from_into"),
+        (opts.boxed_ctor, "boxed_ctor"),
+        (opts.collect_errors, "collect_errors"),
+        (opts.recursive, "recursive"),
+        (opts.debug_checks, "debug_checks"),
+        (opts.metrics.is_some(), "metrics"),
+        (opts.reinterpret, "reinterpret"),
+        (opts.builder_state_mod.is_some(), "builder_state_mod"),
+        (opts.strict, "strict"),
+        (opts.bench, "bench"),
+        (opts.debug_map, "debug_map"),
+        (opts.presence_mask, "presence_mask"),
+        (opts.bridge, "bridge"),
+        (opts.builder, "builder"),
+        (opts.sample, "sample"),
+    ] {
+        if enabled {
+            return syn::Error::new_spanned(
+                original_ident,
+                format!("`{}` is not supported on enums", option),
+            )
+            .to_compile_error();
+        }
+    }
+
+    let unwrapped_ident = &opts.unwrapped_ident(original_ident);
+    let try_from_ident = opts
+        .method_name
+        .clone()
+        .unwrap_or_else(|| format_ident!("try_from"));
+    let source = error_source(opts, unwrapped_ident, &try_from_ident.to_string());
+
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let mirror_generics = mirror_generics(
+        &input.generics,
+        data.variants
+            .iter()
+            .flat_map(|v| v.fields.iter().map(|f| &f.ty)),
+    );
+    let (mirror_impl_generics, mirror_ty_generics, mirror_where_clause) =
+        mirror_generics.split_for_impl();
+
+    let vis: syn::Visibility = if opts.inherit_vis {
+        input.vis.clone()
+    } else {
+        syn::parse_quote!(pub)
+    };
+
+    let common_opts = opts.to_common();
+    let common_proc_opts = proc_usage_opts.to_common();
+
+    let mut variants = Vec::new();
+    let mut from_arms = Vec::new();
+    let mut try_from_arms = Vec::new();
+    let mut field_count = 0usize;
+
+    for v in data.variants.iter() {
+        let variant_ident = &v.ident;
+        // Field attributes are collected per variant, as if it were a struct of its own
+        let variant_struct = syn::DataStruct {
+            struct_token: Default::default(),
+            fields: v.fields.clone(),
+            semi_token: None,
+        };
+
+        let mut fields = Vec::new();
+        let mut bindings = Vec::new();
+        let mut from_values = Vec::new();
+        let mut try_from_values = Vec::new();
+        for (i, f) in variant_struct.fields.iter().enumerate() {
+            let field_opts = FieldOpts::from_field(f).expect("Wrong field options");
+            // A skipped field leaves no value to rebuild the variant from
+            if field_opts.skip {
+                return syn::Error::new_spanned(
+                    f,
+                    "`skip` is not supported on enum variant fields",
+                )
+                .to_compile_error();
+            }
+            for (set, option) in [
+                (field_opts.recursive.is_some(), "recursive"),
+                (field_opts.rename.is_some(), "rename"),
+                (!field_opts.when_tag.is_empty(), "when_tag"),
+                (field_opts.default.is_some(), "default"),
+                (field_opts.default_fn.is_some(), "default_fn"),
+                (field_opts.assert.is_some(), "assert"),
+                (field_opts.sample.is_some(), "sample"),
+                (field_opts.zeroize_skip, "zeroize_skip"),
+                (field_opts.downcast, "downcast"),
+                (field_opts.with.is_some(), "with"),
+            ] {
+                if set {
+                    return syn::Error::new_spanned(
+                        f,
+                        format!("`{}` is not supported on enum variant fields", option),
+                    )
+                    .to_compile_error();
+                }
+            }
+            if let Some(cfg) = cfg_attrs(&f.attrs).first() {
+                return syn::Error::new_spanned(
+                    cfg,
+                    "`#[cfg(...)]` is not supported on enum variant fields",
+                )
+                .to_compile_error();
+            }
+
+            let binding = f
+                .ident
+                .clone()
+                .unwrap_or_else(|| format_ident!("__field{}", i));
+            let label = match &f.ident {
+                Some(name) => format!("{}.{}", variant_ident, raw_ident_name(name)),
+                None => format!("{}.{}", variant_ident, i),
+            };
+            let vis = &f.vis;
+            let name = f.ident.as_ref().map(|name| quote! { #name: });
+
+            let ctx = FieldContext {
+                field: f,
+                index: i,
+                struct_ident: original_ident,
+                transformed: is_option_type(&f.ty).is_some(),
+            };
+            let field_attrs = collect_field_attrs(
+                &variant_struct,
+                &ctx,
+                &field_opts.extra_attrs,
+                &common_opts,
+                &common_proc_opts,
+            );
+            let docs = if opts.no_docs || common_opts.skips_attr("doc") {
+                Vec::new()
+            } else {
+                doc_attrs(&f.attrs)
+            };
+            let attrs = quote! { #(#docs)* #(#field_attrs)* };
+
+            if let Some(inner_ty) = is_option_type(&f.ty) {
+                fields.push(quote! { #attrs #vis #name #inner_ty });
+                from_values.push(quote! { Some(#binding) });
+                let missing = missing_error(lib_path, &label, &source);
+                try_from_values.push(quote! { #binding.ok_or(#missing)? });
+            } else {
+                let ty = &f.ty;
+                fields.push(quote! { #attrs #vis #name #ty });
+                from_values.push(quote! { #binding });
+                try_from_values.push(quote! { #binding });
+            }
+            bindings.push(binding);
+            field_count += 1;
+        }
+
+        let names = v.fields.iter().map(|f| &f.ident);
+        match &v.fields {
+            syn::Fields::Named(_) => {
+                let names: Vec<_> = names.collect();
+                variants.push(quote! { #variant_ident { #(#fields),* } });
+                from_arms.push(quote! {
+                    #unwrapped_ident::#variant_ident { #(#bindings),* } => Self::#variant_ident { #(#names: #from_values),* }
+                });
+                try_from_arms.push(quote! {
+                    #original_ident::#variant_ident { #(#bindings),* } => Self::#variant_ident { #(#names: #try_from_values),* }
+                });
+            },
+            syn::Fields::Unnamed(_) => {
+                variants.push(quote! { #variant_ident(#(#fields),*) });
+                from_arms.push(quote! {
+                    #unwrapped_ident::#variant_ident(#(#bindings),*) => Self::#variant_ident(#(#from_values),*)
+                });
+                try_from_arms.push(quote! {
+                    #original_ident::#variant_ident(#(#bindings),*) => Self::#variant_ident(#(#try_from_values),*)
+                });
+            },
+            syn::Fields::Unit => {
+                variants.push(quote! { #variant_ident });
+                from_arms.push(quote! { #unwrapped_ident::#variant_ident => Self::#variant_ident });
+                try_from_arms
+                    .push(quote! { #original_ident::#variant_ident => Self::#variant_ident });
+            },
+        }
+    }
+
+    let source_name = original_ident.to_string();
+    let mut struct_attrs = opts.struct_attrs.clone();
+    if !common_opts.skips_attr("cfg_attr") {
+        struct_attrs.extend(forwarded_test_attrs(
+            &input.attrs,
+            derives_serde(&opts.struct_derives),
+        ));
+    }
+    let derive_output = build_derive_output(&opts.struct_derives);
+    let forwarded_attrs = forwarded_container_attrs(&input.attrs, &common_opts);
+
+    quote! {
+        #(#struct_attrs)*
+        #derive_output
+        #(#forwarded_attrs)*
+        #vis enum #unwrapped_ident #mirror_impl_generics #mirror_where_clause {
+            #(#variants),*
+        }
+
+        impl #impl_generics ::core::convert::From<#unwrapped_ident #mirror_ty_generics> for #original_ident #ty_generics #where_clause {
+            fn from(from: #unwrapped_ident #mirror_ty_generics) -> Self {
+                match from {
+                    #(#from_arms),*
+                }
+            }
+        }
+
+        impl #impl_generics #lib_path::Unwrapped for #original_ident #ty_generics #where_clause {
+            type Unwrapped = #unwrapped_ident #mirror_ty_generics;
+
+            const FIELD_COUNT: usize = #field_count;
+            const SOURCE: &'static str = #source_name;
+        }
+
+        impl #impl_generics #unwrapped_ident #mirror_ty_generics #where_clause {
+            pub fn #try_from_ident(from: #original_ident #ty_generics) -> ::core::result::Result<Self, #lib_path::UnwrappedError> {
+                Ok(match from {
+                    #(#try_from_arms),*
+                })
+            }
+        }
+
+        impl #impl_generics ::core::convert::TryFrom<#original_ident #ty_generics> for #unwrapped_ident #mirror_ty_generics #where_clause {
+            type Error = #lib_path::UnwrappedError;
+
+            fn try_from(from: #original_ident #ty_generics) -> ::core::result::Result<Self, Self::Error> {
+                Self::#try_from_ident(from)
+            }
+        }
+    }
+}
+
+pub fn unwrapped(
+    input: &DeriveInput,
+    options: Option<Opts>,
+    proc_usage_opts: UnwrappedProcUsageOpts,
+) -> proc_macro2::TokenStream {
+    let opts = options.unwrap_or_else(|| Opts::from_derive_input(input).expect("Wrong options"));
+    let input = &with_where_predicates(input, &opts.where_predicates);
+    let lib_path = opts
+        .krate
+        .clone()
+        .unwrap_or_else(|| proc_usage_opts.lib_path());
+    if let syn::Data::Enum(data) = &input.data {
+        return unwrapped_enum(input, data, &opts, &proc_usage_opts, &lib_path);
+    }
+    let common_opts = opts.to_common();
+    let common_proc_opts = proc_usage_opts.to_common();
+
+    let original_ident = &input.ident;
+    let unwrapped_ident = &opts.unwrapped_ident(original_ident);
+    let try_from_ident = opts
+        .method_name
+        .clone()
+        .unwrap_or_else(|| format_ident!("try_from"));
+
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let s = get_struct_data(input);
+
+    let mirror_generics = mirror_generics(
+        &input.generics,
+        s.fields.iter().filter_map(|f| {
+            let field_opts = opts.field_opts(s, f);
+            (!field_opts.skip).then_some(&f.ty)
+        }),
+    );
+    let (mirror_impl_generics, mirror_ty_generics, mirror_where_clause) =
+        mirror_generics.split_for_impl();
+
+    let vis: syn::Visibility = if opts.inherit_vis {
+        input.vis.clone()
+    } else {
+        syn::parse_quote!(pub)
+    };
+
+    // Every allowlisted name must match a field
+    if let Err(err) = check_field_names(s, &opts.only) {
+        return err.to_compile_error();
+    }
+
+    // A field's default is either an expression or a function, never both
+    if let Some(f) = s.fields.iter().find(|f| {
+        let field_opts = FieldOpts::from_field(f).expect("Wrong field options");
+        field_opts.default.is_some() && field_opts.default_fn.is_some()
+    }) {
+        return syn::Error::new_spanned(f, "`default` and `default_fn` cannot both be set")
+            .to_compile_error();
+    }
+
+    // These helpers generate or convert to structs with named fields
+    if matches!(s.fields, syn::Fields::Unnamed(_)) {
+        for (enabled, helper) in [
+            (opts.view, "view"),
+            (opts.bridge, "bridge"),
+            (opts.builder, "builder"),
+        ] {
+            if enabled {
+                return syn::Error::new_spanned(
+                    original_ident,
+                    format!("`{}` is not supported on tuple structs", helper),
+                )
+                .to_compile_error();
+            }
+        }
+        if let Some(f) = s
+            .fields
+            .iter()
+            .find(|f| opts.field_opts(s, f).rename.is_some())
+        {
+            return syn::Error::new_spanned(f, "`rename` is not supported on tuple struct fields")
+                .to_compile_error();
+        }
+        // Positions on the generated struct are fixed when the macro runs
+        if let Some(cfg) = s
+            .fields
+            .iter()
+            .find_map(|f| cfg_attrs(&f.attrs).first().copied())
+        {
+            return syn::Error::new_spanned(
+                cfg,
+                "`#[cfg(...)]` is not supported on tuple struct fields",
+            )
+            .to_compile_error();
+        }
+    }
+
+    // Under `strict`, a `skip_deserializing` field that loses its `Option` must be filled by
+    // `Default` on deserialization, which silently replaces the `None` serde would have produced
+    if opts.strict {
+        for f in &s.fields {
+            let field_opts = opts.field_opts(s, f);
+            if !field_opts.skip
+                && unwraps_field(s, f, &field_opts, &proc_usage_opts)
+                && (has_serde_flag(f, "skip_deserializing") || has_serde_flag(f, "skip"))
+            {
+                return syn::Error::new_spanned(
+                    f,
+                    "`skip_deserializing` field is unwrapped, so deserializing fills it with `Default::default()` instead of `None`; skip it with `#[unwrapped(skip)]` or relax `strict`",
+                )
+                .to_compile_error();
+            }
+        }
+    }
+
+    // Nothing to unwrap means the generated struct would be a copy of the original, so alias it.
+    // A skipped, renamed or unwrapped field changes the shape and falls back to a real struct
+    if opts.transparent
+        && s.fields.iter().all(|f| {
+            let field_opts = opts.field_opts(s, f);
+            !field_opts.skip
+                && field_opts.rename.is_none()
+                && !unwraps_field(s, f, &field_opts, &proc_usage_opts)
+                && boxed_option_field(s, f, &field_opts, &proc_usage_opts).is_none()
+                && option_like_field(s, f, &field_opts, &proc_usage_opts).is_none()
+        })
+    {
+        // Bounds on a type alias are not enforced, so only the parameters are declared
+        let alias_params: Vec<_> = input
+            .generics
+            .params
+            .iter()
+            .map(|param| match param {
+                syn::GenericParam::Lifetime(lt) => {
+                    let lifetime = &lt.lifetime;
+                    quote! { #lifetime }
+                },
+                syn::GenericParam::Type(ty) => {
+                    let ident = &ty.ident;
+                    quote! { #ident }
+                },
+                syn::GenericParam::Const(c) => {
+                    let ident = &c.ident;
+                    let ty = &c.ty;
+                    quote! { const #ident: #ty }
+                },
+            })
+            .collect();
+        let alias_generics = if alias_params.is_empty() {
+            quote! {}
+        } else {
+            quote! { <#(#alias_params),*> }
+        };
+        let field_count = field_count(s.fields.iter());
+        let source_name = original_ident.to_string();
+        return quote! {
+            #vis type #unwrapped_ident #alias_generics = #original_ident #ty_generics;
+
+            const _: () = {
+                impl #impl_generics #lib_path::Unwrapped for #original_ident #ty_generics #where_clause {
+                    type Unwrapped = Self;
+
+                    const FIELD_COUNT: usize = #field_count;
+                    const SOURCE: &'static str = #source_name;
+                }
+            };
+        };
+    }
+
+    let source = error_source(&opts, unwrapped_ident, &try_from_ident.to_string());
+    let view_source = error_source(&opts, unwrapped_ident, "view");
+    let view_tag_checks = match tag_checks(s, opts.tag.as_ref(), &lib_path, &view_source) {
+        Ok(checks) => checks,
+        Err(err) => return err.to_compile_error(),
+    };
+    let borrow_source = error_source(&opts, original_ident, "try_as_unwrapped");
+    let borrow_tag_checks = match tag_checks(s, opts.tag.as_ref(), &lib_path, &borrow_source) {
+        Ok(checks) => checks,
+        Err(err) => return err.to_compile_error(),
+    };
+    let tag_checks = match tag_checks(s, opts.tag.as_ref(), &lib_path, &source) {
+        Ok(checks) => checks,
+        Err(err) => return err.to_compile_error(),
+    };
+
+    // `with` can't name its result type, so it comes with `ty`
+    for f in &s.fields {
+        let field_opts = opts.field_opts(s, f);
+        let message = match (&field_opts.with, &field_opts.with_ty, &field_opts.back) {
+            (Some(_), None, _) => "`with` needs the type it converts to, e.g. `ty = u16`",
+            (None, Some(_), _) => "`ty` only applies to fields with a `with` function",
+            (None, _, Some(_)) => "`back` only applies to fields with a `with` function",
+            _ => continue,
+        };
+        return syn::Error::new_spanned(f, message).to_compile_error();
+    }
+
+    // Check if any field has skip attribute
+    let has_skipped_fields = s.fields.iter().any(|f| {
+        let field_opts = opts.field_opts(s, f);
+        field_opts.skip
+    });
+
+    let fields = gated_fields(&s.fields, |f| {
+        let field_opts = opts.field_opts(s, f);
+
+        // Skip this field entirely if skip attribute is present
+        if field_opts.skip {
+            return None;
+        }
+
+        // Tuple struct fields are declared without a name
+        let name = f.ident.as_ref().map(|_| {
+            let name = opts.generated_member(s, f);
+            quote! { #name: }
+        });
+        let ty = &f.ty;
+
+        // Collect field attributes
+        let ctx = FieldContext {
+            field: f,
+            index: field_position(s, f),
+            struct_ident: original_ident,
+            transformed: unwraps_field(s, f, &field_opts, &proc_usage_opts)
+                || boxed_option_field(s, f, &field_opts, &proc_usage_opts).is_some()
+                || option_like_field(s, f, &field_opts, &proc_usage_opts).is_some(),
+        };
+        let mut field_attrs = collect_field_attrs(
+            s,
+            &ctx,
+            &field_opts.extra_attrs,
+            &common_opts,
+            &common_proc_opts,
+        );
+        if opts.zeroize && field_opts.zeroize_skip {
+            field_attrs.push(quote! { #[zeroize(skip)] });
+        }
+
+        // Doc comments go first, ahead of every collected attribute
+        let docs = if opts.no_docs || common_opts.skips_attr("doc") {
+            Vec::new()
+        } else {
+            doc_attrs(&f.attrs)
+        };
+        let field_vis = opts.field_vis(f);
+
+        if let Some(inner_ty) = recursive_field(s, f, &field_opts, &proc_usage_opts) {
+            return Some(
+                quote! { #(#docs)* #(#field_attrs)* #field_vis #name <#inner_ty as #lib_path::Unwrapped>::Unwrapped },
+            );
+        }
+        if let Some(inner_ty) = boxed_option_field(s, f, &field_opts, &proc_usage_opts) {
+            return Some(
+                quote! { #(#docs)* #(#field_attrs)* #field_vis #name ::std::boxed::Box<#inner_ty> },
+            );
+        }
+        if unwraps_field(s, f, &field_opts, &proc_usage_opts)
+            && let Some(inner_ty) = is_option_type(ty)
+        {
+            return Some(quote! { #(#docs)* #(#field_attrs)* #field_vis #name #inner_ty });
+        }
+        if let Some(OptionLike { inner_ty, .. }) =
+            option_like_field(s, f, &field_opts, &proc_usage_opts)
+        {
+            return Some(quote! { #(#docs)* #(#field_attrs)* #field_vis #name #inner_ty });
+        }
+        Some(quote! { #(#docs)* #(#field_attrs)* #field_vis #name #ty })
+    });
+
+    let from_fields = gated_fields(&s.fields, |f| {
+        let field_opts = opts.field_opts(s, f);
+
+        // Skip this field if skip attribute is present
+        if field_opts.skip {
+            return None;
+        }
+
+        let name = field_member(s, f);
+        let uw_name = opts.generated_member(s, f);
+
+        if recursive_field(s, f, &field_opts, &proc_usage_opts).is_some() {
+            return Some(quote! { #name: Some(::core::convert::From::from(from.#uw_name)) });
+        }
+        if boxed_option_field(s, f, &field_opts, &proc_usage_opts).is_some() {
+            return Some(quote! { #name: ::std::boxed::Box::new(Some(*from.#uw_name)) });
+        }
+        if unwraps_field(s, f, &field_opts, &proc_usage_opts) {
+            return Some(quote! { #name: Some(from.#uw_name) });
+        }
+        if let Some(option_like) = option_like_field(s, f, &field_opts, &proc_usage_opts) {
+            let value = option_like.convert_back(quote! { from.#uw_name });
+            return Some(quote! { #name: #value });
+        }
+        Some(quote! { #name: from.#uw_name })
+    });
+
+    // A field of the converted struct, read from `from` by value, or cloned out of it when
+    // `borrowed` because `from` is a reference
+    let try_from_field = |f: &syn::Field, borrowed: bool, source: &proc_macro2::TokenStream| {
+        let field_opts = opts.field_opts(s, f);
+
+        // Skip this field if skip attribute is present
+        if field_opts.skip {
+            return None;
+        }
+
+        let name = field_member(s, f);
+        let uw_name = opts.generated_member(s, f);
+        let field_name_str = field_key(s, f);
+        let missing = missing_error(&lib_path, &field_name_str, source);
+        let field = if borrowed {
+            quote! { ::core::clone::Clone::clone(&from.#name) }
+        } else {
+            quote! { from.#name }
+        };
+
+        let value = if let Some(inner_ty) = recursive_field(s, f, &field_opts, &proc_usage_opts) {
+            // The nested conversion's error names its field by path, e.g. `server.port`
+            quote! {
+                <<#inner_ty as #lib_path::Unwrapped>::Unwrapped as ::core::convert::TryFrom<#inner_ty>>::try_from(
+                    #field.ok_or(#missing)?
+                )
+                .map_err(|err| {
+                    let err: #lib_path::UnwrappedError = err.into();
+                    #lib_path::UnwrappedError {
+                        field_name: ::std::borrow::Cow::Owned(::std::format!("{}.{}", #field_name_str, err.field_name)),
+                        ..err
+                    }
+                })?
+            }
+        } else if boxed_option_field(s, f, &field_opts, &proc_usage_opts).is_some() {
+            let inner = if borrowed {
+                quote! { ::core::clone::Clone::clone(&*from.#name) }
+            } else {
+                quote! { (*from.#name) }
+            };
+            quote! {
+                ::std::boxed::Box::new(
+                    #inner.ok_or(#missing)?
+                )
+            }
+        } else if unwraps_field(s, f, &field_opts, &proc_usage_opts) {
+            quote! { #field.ok_or(#missing)? }
+        } else if let Some(option_like) = option_like_field(s, f, &field_opts, &proc_usage_opts) {
+            let extracted = option_like.extract(field);
+            let error = unwrapped_error(
+                &lib_path,
+                &field_name_str,
+                source,
+                quote! { conversion(err) },
+            );
+            quote! { #extracted.map_err(|err| #error)? }
+        } else {
+            field
+        };
+
+        // The assertion runs after the presence check, so `value` is already unwrapped
+        if let Some(assertion) = &field_opts.assert {
+            let error = unwrapped_error(&lib_path, &field_name_str, source, quote! { Assertion });
+            return Some(quote! {
+                #uw_name: {
+                    let value = #value;
+                    if !(#assertion) {
+                        return Err(#error);
+                    }
+                    value
+                }
+            });
+        }
+        Some(quote! { #uw_name: #value })
+    };
+    let try_from_fields = gated_fields(&s.fields, |f| try_from_field(f, false, &source));
+
+    // Generate try_as_unwrapped - the same checks as `try_from`, over clones of borrowed fields
+    let borrow_helper = if opts.borrow {
+        let borrow_fields = gated_fields(&s.fields, |f| try_from_field(f, true, &borrow_source));
+        quote! {
+            impl #impl_generics #original_ident #ty_generics #where_clause {
+                /// Like `try_from`, but clones each field out of `self` instead of consuming it.
+                pub fn try_as_unwrapped(&self) -> ::core::result::Result<#unwrapped_ident #mirror_ty_generics, #lib_path::UnwrappedError> {
+                    let from = self;
+                    #(#borrow_tag_checks)*
+                    Ok(#unwrapped_ident {
+                        #(#borrow_fields),*
+                    })
+                }
+            }
+        }
+    } else {
+        quote! {}
+    };
+
+    // Debug-only round-trip checks, compiled out entirely without `debug_assertions`
+    let (debug_snapshot, debug_check) = if opts.debug_checks {
+        let checks = gated_fields(&s.fields, |f| {
+            let field_opts = opts.field_opts(s, f);
+            // Wrapper values cannot be compared with their extracted value
+            if field_opts.skip
+                || option_like_field(s, f, &field_opts, &proc_usage_opts).is_some()
+                || recursive_field(s, f, &field_opts, &proc_usage_opts).is_some()
+                || boxed_option_field(s, f, &field_opts, &proc_usage_opts).is_some()
+            {
+                return None;
+            }
+
+            let name = field_member(s, f);
+            let uw_name = opts.generated_member(s, f);
+            let message = format!("`{}` changed during unwrapping", field_key(s, f));
+            Some(if unwraps_field(s, f, &field_opts, &proc_usage_opts) {
+                quote! { debug_assert!(__original.#name.as_ref() == Some(&unwrapped.#uw_name), #message); }
+            } else {
+                quote! { debug_assert!(__original.#name == unwrapped.#uw_name, #message); }
+            })
+        });
+
+        (
+            quote! {
+                #[cfg(debug_assertions)]
+                let __original = ::core::clone::Clone::clone(&from);
+            },
+            quote! {
+                #[cfg(debug_assertions)]
+                {
+                    #(#checks)*
+                }
+            },
+        )
+    } else {
+        (quote! {}, quote! {})
+    };
+
+    // Metrics calls, one per unwrapped field in declaration order, made before any field is checked
+    // so a failing conversion still reports every field
+    let metrics_calls: Vec<_> = match &opts.metrics {
+        Some(record_fn) => gated_fields(&s.fields, |f| {
+            let field_opts = opts.field_opts(s, f);
+            if field_opts.skip {
+                return None;
+            }
+
+            let name = field_member(s, f);
+            let field_name_str = field_key(s, f);
+            if unwraps_field(s, f, &field_opts, &proc_usage_opts) {
+                Some(quote! { #record_fn(#field_name_str, from.#name.is_some()); })
+            } else if boxed_option_field(s, f, &field_opts, &proc_usage_opts).is_some() {
+                Some(quote! { #record_fn(#field_name_str, (*from.#name).is_some()); })
+            } else {
+                None
+            }
+        })
+        .collect(),
+        None => Vec::new(),
+    };
+
+    // Generate for_each_string_field - only fields that are `String` on the generated struct
+    let string_visitor = if opts.string_visitor {
+        let visits = gated_fields(&s.fields, |f| {
+            let field_opts = opts.field_opts(s, f);
+            if field_opts.skip {
+                return None;
+            }
+
+            let uw_name = opts.generated_member(s, f);
+            let generated_ty = if unwraps_field(s, f, &field_opts, &proc_usage_opts) {
+                is_option_type(&f.ty).unwrap()
+            } else if let Some(OptionLike { inner_ty, .. }) =
+                option_like_field(s, f, &field_opts, &proc_usage_opts)
+            {
+                inner_ty
+            } else {
+                &f.ty
+            };
+
+            is_string_type(generated_ty).then(|| quote! { f(&mut self.#uw_name); })
+        });
+
+        quote! {
+            impl #impl_generics #unwrapped_ident #mirror_ty_generics #where_clause {
+                /// Apply `f` to every `String` field, in declaration order.
+                #[allow(unused_mut, unused_variables)]
+                pub fn for_each_string_field(&mut self, mut f: impl FnMut(&mut String)) {
+                    #(#visits)*
+                }
+            }
+        }
+    } else {
+        quote! {}
+    };
+
+    // Generate <field>_as - typed access to `Box<dyn Any>` fields through `downcast_ref`
+    let mut downcast_accessors = Vec::new();
+    for f in &s.fields {
+        let field_opts = opts.field_opts(s, f);
+        if !field_opts.downcast {
+            continue;
+        }
+        let Some(ident) = &f.ident else {
+            return syn::Error::new_spanned(f, "`downcast` needs a named field").to_compile_error();
+        };
+        let generated_ty = if field_opts.skip
+            || recursive_field(s, f, &field_opts, &proc_usage_opts).is_some()
+            || boxed_option_field(s, f, &field_opts, &proc_usage_opts).is_some()
+        {
+            None
+        } else if unwraps_field(s, f, &field_opts, &proc_usage_opts) {
+            is_option_type(&f.ty)
+        } else if let Some(OptionLike { inner_ty, .. }) =
+            option_like_field(s, f, &field_opts, &proc_usage_opts)
+        {
+            Some(inner_ty)
+        } else {
+            Some(&f.ty)
+        };
+        if !generated_ty.is_some_and(is_dyn_any_pointer) {
+            return syn::Error::new_spanned(
+                &f.ty,
+                "`downcast` needs a field that is `Box<dyn Any>` on the generated struct",
+            )
+            .to_compile_error();
+        }
+
+        let uw_name = opts.generated_member(s, f);
+        let method = format_ident!("{}_as", raw_ident_name(ident));
+        let doc = format!(
+            "`{}` as a `T`, or `None` when it holds a value of another type.",
+            field_key(s, f)
+        );
+        let cfgs = cfg_attrs(&f.attrs);
+        downcast_accessors.push(quote! {
+            #(#cfgs)*
+            #[doc = #doc]
+            pub fn #method<__DowncastT: ::core::any::Any>(&self) -> ::core::option::Option<&__DowncastT> {
+                (*self.#uw_name).downcast_ref::<__DowncastT>()
+            }
+        });
+    }
+    let downcast_helper = if downcast_accessors.is_empty() {
+        quote! {}
+    } else {
+        quote! {
+            impl #impl_generics #unwrapped_ident #mirror_ty_generics #where_clause {
+                #(#downcast_accessors)*
+            }
+        }
+    };
+
+    // Generate {Original}View - `String` and `Vec<T>` are viewed as `&str` and `&[T]`, everything
+    // else as `&T`, so no field is cloned
+    let view_helper = if opts.view {
+        let view_ident = format_ident!("{}View", original_ident);
+        let mut view_generics = mirror_generics.clone();
+        view_generics.params.insert(0, syn::parse_quote!('__view));
+        let (view_impl_generics, view_ty_generics, view_where_clause) =
+            view_generics.split_for_impl();
+
+        // The borrowed type and the `Option` method that produces it
+        let borrowed = |ty: &syn::Type| -> (proc_macro2::TokenStream, proc_macro2::TokenStream) {
+            if is_string_type(ty) {
+                (quote! { &'__view str }, quote! { as_deref })
+            } else if let Some(elem_ty) = is_vec_type(ty) {
+                (quote! { &'__view [#elem_ty] }, quote! { as_deref })
+            } else {
+                (quote! { &'__view #ty }, quote! { as_ref })
+            }
+        };
+
+        let (view_fields, view_inits): (Vec<_>, Vec<_>) = s
+            .fields
+            .iter()
+            .filter_map(|f| {
+                let field_opts = opts.field_opts(s, f);
+                if field_opts.skip {
+                    return None;
+                }
+
+                let name = &f.ident;
+                let vis = opts.field_vis(f);
+                let cfgs = cfg_attrs(&f.attrs);
+                // The view can't take `attr(...)`, so a private field it only fills must not warn
+                let allow = (!matches!(vis, syn::Visibility::Public(_)))
+                    .then(|| quote! { #[allow(dead_code)] });
+                let (view_field, view_init) = match is_option_type(&f.ty) {
+                    Some(inner_ty) if unwraps_field(s, f, &field_opts, &proc_usage_opts) => {
+                        let missing = missing_error(
+                            &lib_path,
+                            &name.as_ref().unwrap().to_string(),
+                            &view_source,
+                        );
+                        let (view_ty, borrow) = borrowed(inner_ty);
+                        (
+                            quote! { #vis #name: #view_ty },
+                            quote! { #name: ::core::option::Option::#borrow(&from.#name).ok_or(#missing)? },
+                        )
+                    },
+                    Some(inner_ty) => {
+                        let (view_ty, borrow) = borrowed(inner_ty);
+                        (
+                            quote! { #vis #name: ::core::option::Option<#view_ty> },
+                            quote! { #name: ::core::option::Option::#borrow(&from.#name) },
+                        )
+                    },
+                    None => {
+                        let (view_ty, _) = borrowed(&f.ty);
+                        (quote! { #vis #name: #view_ty }, quote! { #name: &from.#name })
+                    },
+                };
+                Some((
+                    quote! { #(#cfgs)* #allow #view_field },
+                    quote! { #(#cfgs)* #view_init },
+                ))
+            })
+            .unzip();
+
+        quote! {
+            /// Borrowed view of the original struct with its required fields unwrapped.
+            #[derive(Clone, Copy)]
+            #vis struct #view_ident #view_impl_generics #view_where_clause {
+                #(#view_fields,)*
+            }
+
+            impl #impl_generics #original_ident #ty_generics #where_clause {
+                /// Borrow every field, failing on the first required field that is `None`.
+                pub fn view<'__view>(&'__view self) -> ::core::result::Result<#view_ident #view_ty_generics, #lib_path::UnwrappedError> {
+                    let from = self;
+                    #(#view_tag_checks)*
+                    Ok(#view_ident {
+                        #(#view_inits,)*
+                    })
+                }
+            }
+        }
+    } else {
+        quote! {}
+    };
+
+    // Generate to_debug_map - one `Debug` rendering per field of the generated struct
+    let debug_map_helper = if opts.debug_map {
+        let entries = gated_fields(&s.fields, |f| {
+            let field_opts = opts.field_opts(s, f);
+            if field_opts.skip {
+                return None;
+            }
+
+            let uw_name = opts.generated_member(s, f);
+            let field_name_str = field_key(s, f);
+            Some(quote! {
+                map.insert(::std::string::String::from(#field_name_str), ::std::format!("{:?}", self.#uw_name));
+            })
+        });
+
+        quote! {
+            impl #impl_generics #unwrapped_ident #mirror_ty_generics #where_clause {
+                /// Every field formatted with `Debug`, keyed by field name, for structured logging.
+                #[allow(unused_mut)]
+                pub fn to_debug_map(&self) -> ::std::collections::HashMap<::std::string::String, ::std::string::String> {
+                    let mut map = ::std::collections::HashMap::new();
+                    #(#entries)*
+                    map
+                }
+            }
+        }
+    } else {
+        quote! {}
+    };
+
+    // Generate expect_unwrapped - panics at the caller's location instead of inside the conversion
+    let expect_helper = if opts.expect {
+        let original_name = original_ident.to_string();
+        quote! {
+            impl #impl_generics #unwrapped_ident #mirror_ty_generics #where_clause {
+                /// Like `try_from`, but panics with the failing field name.
+                ///
+                /// The panic location points at the caller, which makes this handy in tests.
+                #[track_caller]
+                pub fn expect_unwrapped(from: #original_ident #ty_generics) -> Self {
+                    match Self::#try_from_ident(from) {
+                        Ok(unwrapped) => unwrapped,
+                        Err(err) => ::core::panic!("failed to unwrap `{}`: {}", #original_name, err),
+                    }
+                }
+            }
+        }
+    } else {
+        quote! {}
+    };
+
+    // Generate try_split - the original is cloned first, so both halves are independent values
+    let split_helper = if opts.split {
+        quote! {
+            impl #impl_generics #unwrapped_ident #mirror_ty_generics #where_clause {
+                /// Convert a clone of `original` like `try_from`, returning the original alongside.
+                ///
+                /// The clone is taken before converting, so the two values share nothing.
+                pub fn try_split(
+                    original: #original_ident #ty_generics,
+                ) -> ::core::result::Result<(#original_ident #ty_generics, Self), #lib_path::UnwrappedError>
+                where
+                    #original_ident #ty_generics: ::core::clone::Clone,
+                {
+                    let unwrapped = Self::#try_from_ident(::core::clone::Clone::clone(&original))?;
+                    Ok((original, unwrapped))
+                }
+            }
+        }
+    } else {
+        quote! {}
+    };
+
+    // Generate analyze - a read-only presence report for every field of the generated struct
+    let analyze_helper = if opts.analyze {
+        // A `when_tag` field is only required, and reported as such, while its tag requires it
+        let tag_required = match tag_conditions(s, opts.tag.as_ref(), &quote! { original }) {
+            Ok(conditions) => conditions,
+            Err(err) => return err.to_compile_error(),
+        };
+        let entries = gated_fields(&s.fields, |f| {
+            let field_opts = opts.field_opts(s, f);
+            if field_opts.skip {
+                return None;
+            }
+
+            let name = field_member(s, f);
+            let field_name_str = field_key(s, f);
+            let presence = if let Some((_, required)) =
+                tag_required.iter().find(|(tf, _)| std::ptr::eq(*tf, f))
+            {
+                quote! {
+                    if !#required {
+                        #lib_path::Presence::NotOptional
+                    } else if original.#name.is_some() {
+                        #lib_path::Presence::Present
+                    } else {
+                        #lib_path::Presence::Missing
+                    }
+                }
+            } else if unwraps_field(s, f, &field_opts, &proc_usage_opts)
+                || boxed_option_field(s, f, &field_opts, &proc_usage_opts).is_some()
+            {
+                quote! {
+                    if original.#name.is_some() {
+                        #lib_path::Presence::Present
+                    } else {
+                        #lib_path::Presence::Missing
+                    }
+                }
+            } else {
+                quote! { #lib_path::Presence::NotOptional }
+            };
+            Some(quote! { (#field_name_str, #presence) })
+        });
+
+        quote! {
+            impl #impl_generics #unwrapped_ident #mirror_ty_generics #where_clause {
+                /// Report the presence of every field of `original`, in declaration order.
+                ///
+                /// Nothing is converted, so this is cheap to call on data that may be incomplete.
+                pub fn analyze(original: &#original_ident #ty_generics) -> ::std::vec::Vec<(&'static str, #lib_path::Presence)> {
+                    ::std::vec![#(#entries),*]
+                }
+            }
+        }
+    } else {
+        quote! {}
+    };
+
+    // Generate missing_fields - the names `try_from` would report, checked without converting
+    let missing_fields_helper = if opts.missing_fields {
+        // A `when_tag` field is missing only while its tag requires it
+        let tag_required = match tag_conditions(s, opts.tag.as_ref(), &quote! { original }) {
+            Ok(conditions) => conditions,
+            Err(err) => return err.to_compile_error(),
+        };
+        let checks = gated_fields(&s.fields, |f| {
+            let field_opts = opts.field_opts(s, f);
+            if field_opts.skip {
+                return None;
+            }
+
+            let name = field_member(s, f);
+            let field_name_str = field_key(s, f);
+            if let Some((_, required)) = tag_required.iter().find(|(tf, _)| std::ptr::eq(*tf, f)) {
+                Some(
+                    quote! { if #required && original.#name.is_none() { missing.push(#field_name_str); } },
+                )
+            } else if unwraps_field(s, f, &field_opts, &proc_usage_opts) {
+                Some(quote! { if original.#name.is_none() { missing.push(#field_name_str); } })
+            } else if boxed_option_field(s, f, &field_opts, &proc_usage_opts).is_some() {
+                Some(quote! { if (*original.#name).is_none() { missing.push(#field_name_str); } })
+            } else {
+                None
+            }
+        });
+
+        quote! {
+            impl #impl_generics #unwrapped_ident #mirror_ty_generics #where_clause {
+                /// Names of the fields of `original` that are `None`, in declaration order.
+                ///
+                /// These are the fields `try_from` would report, found without converting anything.
+                #[allow(unused_mut)]
+                pub fn missing_fields(original: &#original_ident #ty_generics) -> ::std::vec::Vec<&'static str> {
+                    let mut missing = ::std::vec::Vec::new();
+                    #(#checks)*
+                    missing
+                }
+            }
+        }
+    } else {
+        quote! {}
+    };
+
+    // Generate try_from_counted - counts every non-skipped `Option` field that holds `Some`
+    let counted_helper = if opts.counted {
+        let option_fields = gated_fields(&s.fields, |f| {
+            let field_opts = opts.field_opts(s, f);
+            if field_opts.skip || is_option_type(&f.ty).is_none() {
+                return None;
+            }
+            let name = field_member(s, f);
+            Some(quote! { { present += usize::from(from.#name.is_some()); } })
+        });
+
+        quote! {
+            impl #impl_generics #unwrapped_ident #mirror_ty_generics #where_clause {
+                /// Like `try_from`, but also returns the number of populated `Option` fields.
+                ///
+                /// Unwrapped fields always count on success. Fields that stay `Option<T>` count when
+                /// they are `Some`, and non-`Option` fields never count.
+                pub fn try_from_counted(from: #original_ident #ty_generics) -> ::core::result::Result<(Self, usize), #lib_path::UnwrappedError> {
+                    let mut present = 0usize;
+                    #(#option_fields)*
+                    Self::#try_from_ident(from).map(|unwrapped| (unwrapped, present))
+                }
+            }
+        }
+    } else {
+        quote! {}
+    };
+
+    // Generate __bench_try_from - `#[inline(never)]` keeps the conversion a distinct call in benchmarks
+    let bench_helper = if opts.bench {
+        if !cfg!(feature = "bench") {
+            return syn::Error::new_spanned(
+                original_ident,
+                "`bench` requires the `bench` feature of `unwrapped`",
+            )
+            .to_compile_error();
+        }
+        quote! {
+            impl #impl_generics #unwrapped_ident #mirror_ty_generics #where_clause {
+                #[doc(hidden)]
+                #[inline(never)]
+                pub fn __bench_try_from(original: #original_ident #ty_generics) -> ::core::result::Result<Self, #lib_path::UnwrappedError> {
+                    Self::#try_from_ident(original)
+                }
+            }
+        }
+    } else {
+        quote! {}
+    };
+
+    // Generate try_from_into - the conversion with its error converted into the caller's error type
+    let try_from_into_helper = quote! {
+        impl #impl_generics #unwrapped_ident #mirror_ty_generics #where_clause {
+            /// Like `try_from`, but converts the error into any `E: From<UnwrappedError>`.
+            pub fn try_from_into<E: ::core::convert::From<#lib_path::UnwrappedError>>(from: #original_ident #ty_generics) -> ::core::result::Result<Self, E> {
+                Self::#try_from_ident(from).map_err(E::from)
+            }
+        }
+    };
+
+    // Generate try_from_all - report every `None` field at once, then let `try_from` convert
+    let try_from_all_helper = {
+        // A `when_tag` field is checked while its tag requires it, like in `try_from`
+        let tag_required = match tag_conditions(s, opts.tag.as_ref(), &quote! { from }) {
+            Ok(conditions) => conditions,
+            Err(err) => return err.to_compile_error(),
+        };
+        let checks = gated_fields(&s.fields, |f| {
+            let field_opts = opts.field_opts(s, f);
+            let required = tag_required
+                .iter()
+                .find(|(tf, _)| std::ptr::eq(*tf, f))
+                .map(|(_, required)| quote! { #required && });
+            if field_opts.skip
+                || !(required.is_some()
+                    || unwraps_field(s, f, &field_opts, &proc_usage_opts)
+                    || boxed_option_field(s, f, &field_opts, &proc_usage_opts).is_some())
+            {
+                return None;
+            }
+
+            let name = field_member(s, f);
+            let missing = missing_error(&lib_path, &field_key(s, f), &source);
+            Some(quote! {
+                if #required from.#name.is_none() {
+                    errors.push(#missing);
+                }
+            })
+        });
+
+        quote! {
+            impl #impl_generics #unwrapped_ident #mirror_ty_generics #where_clause {
+                /// Like `try_from`, but reports every field that is `None` instead of only the first.
+                ///
+                /// Other failures, such as a failed `assert`, stop at the first error.
+                #[allow(unused_mut)]
+                pub fn try_from_all(from: #original_ident #ty_generics) -> ::core::result::Result<Self, #lib_path::UnwrappedErrors> {
+                    let mut errors = ::std::vec::Vec::new();
+                    #(#checks)*
+                    if !errors.is_empty() {
+                        return Err(#lib_path::UnwrappedErrors(errors));
+                    }
+                    Self::#try_from_ident(from).map_err(|err| #lib_path::UnwrappedErrors(::std::vec![err]))
+                }
+            }
+        }
+    };
+
+    // Generate try_from_partial - `try_from_all` split into an optional struct and a plain error list,
+    // the struct can't be built partially since its fields are required
+    let try_from_partial_helper = if opts.collect_errors {
+        quote! {
+            impl #impl_generics #unwrapped_ident #mirror_ty_generics #where_clause {
+                /// Like `try_from_all`, but returns the struct and the errors side by side.
+                ///
+                /// The struct is `Some` exactly when the error list is empty.
+                pub fn try_from_partial(from: #original_ident #ty_generics) -> (::core::option::Option<Self>, ::std::vec::Vec<#lib_path::UnwrappedError>) {
+                    match Self::try_from_all(from) {
+                        Ok(unwrapped) => (Some(unwrapped), ::std::vec::Vec::new()),
+                        Err(errors) => (None, errors.0),
+                    }
+                }
+            }
+        }
+    } else {
+        quote! {}
+    };
+
+    // Generate From<Original> - an infallible conversion where `None` falls back to a default,
+    // `try_from` and its `assert`s are not involved
+    let default_from_impl = if opts.default_from {
+        let default_fields = gated_fields(&s.fields, |f| {
+            let field_opts = opts.field_opts(s, f);
+            if field_opts.skip {
+                return None;
+            }
+
+            let name = field_member(s, f);
+            let uw_name = opts.generated_member(s, f);
+            let fallback = match &field_opts.default {
+                Some(default) => quote! { #default },
+                None => quote! { ::core::default::Default::default() },
+            };
+            // Serde's `default = "path"` on an `Option<T>` field returns `Option<T>`, so it is
+            // tried before the fallback. The field's own default takes precedence over it
+            let serde_default = serde_default_fn(f).filter(|_| field_opts.default.is_none());
+            Some(
+                if recursive_field(s, f, &field_opts, &proc_usage_opts).is_some() {
+                    // A nested conversion that fails falls back like a `None`
+                    quote! {
+                        #uw_name: from.#name
+                            .and_then(|value| ::core::convert::TryFrom::try_from(value).ok())
+                            .unwrap_or_else(|| #fallback)
+                    }
+                } else if boxed_option_field(s, f, &field_opts, &proc_usage_opts).is_some() {
+                    quote! { #uw_name: (*from.#name).map(::std::boxed::Box::new).unwrap_or_else(|| #fallback) }
+                } else if unwraps_field(s, f, &field_opts, &proc_usage_opts) {
+                    match serde_default {
+                        Some(default_fn) => quote! {
+                            #uw_name: from.#name.or_else(|| #default_fn()).unwrap_or_else(|| #fallback)
+                        },
+                        None => quote! { #uw_name: from.#name.unwrap_or_else(|| #fallback) },
+                    }
+                } else if let Some(option_like) =
+                    option_like_field(s, f, &field_opts, &proc_usage_opts)
+                {
+                    let extracted = option_like.extract(quote! { from.#name });
+                    quote! { #uw_name: #extracted.unwrap_or_else(|_| #fallback) }
+                } else {
+                    quote! { #uw_name: from.#name }
+                },
+            )
+        });
+
+        quote! {
+            impl #impl_generics ::core::convert::From<#original_ident #ty_generics> for #unwrapped_ident #mirror_ty_generics #where_clause {
+                fn from(from: #original_ident #ty_generics) -> Self {
+                    Self {
+                        #(#default_fields),*
+                    }
+                }
+            }
+        }
+    } else {
+        quote! {}
+    };
+
+    // Generate TryFrom<Original> so `try_into()` works, unless `default_from` already provides it
+    // through core's blanket impl over `From`
+    let try_from_trait_impl = if opts.default_from {
+        quote! {}
+    } else {
+        quote! {
+            impl #impl_generics ::core::convert::TryFrom<#original_ident #ty_generics> for #unwrapped_ident #mirror_ty_generics #where_clause {
+                type Error = #lib_path::UnwrappedError;
+
+                fn try_from(from: #original_ident #ty_generics) -> ::core::result::Result<Self, Self::Error> {
+                    Self::#try_from_ident(from)
+                }
+            }
+        }
+    };
+
+    // Generate from_into - a blanket `impl<T: Into<Original>> TryFrom<T>` would overlap core's
+    // reflexive impls, so an associated function is the closest coherent alternative
+    let from_into_helper = if opts.from_into {
+        quote! {
+            impl #impl_generics #unwrapped_ident #mirror_ty_generics #where_clause {
+                /// Convert anything that converts into the original struct, then unwrap it.
+                pub fn from_into<__UnwrappedFrom>(value: __UnwrappedFrom) -> ::core::result::Result<Self, #lib_path::UnwrappedError>
+                where
+                    __UnwrappedFrom: ::core::convert::Into<#original_ident #ty_generics>,
+                {
+                    Self::#try_from_ident(value.into())
+                }
+            }
+        }
+    } else {
+        quote! {}
+    };
+
+    // Generate try_from_boxed - the box is consumed and unboxed straight into `try_from`, so callers
+    // holding a large original behind a `Box` don't move it out themselves
+    let boxed_ctor_helper = if opts.boxed_ctor {
+        quote! {
+            impl #impl_generics #unwrapped_ident #mirror_ty_generics #where_clause {
+                /// Like `try_from`, but consumes a boxed original.
+                pub fn try_from_boxed(from: ::std::boxed::Box<#original_ident #ty_generics>) -> ::core::result::Result<Self, #lib_path::UnwrappedError> {
+                    Self::#try_from_ident(*from)
+                }
+            }
+        }
+    } else {
+        quote! {}
+    };
+
+    // Generate presence_mask - bit `i` is the `i`-th non-skipped field in declaration order
+    let presence_mask_helper = if opts.presence_mask {
+        let kept: Vec<&syn::Field> = s
+            .fields
+            .iter()
+            .filter(|f| !opts.field_opts(s, f).skip)
+            .collect();
+        if kept.len() > 64 {
+            return syn::Error::new_spanned(
+                original_ident,
+                format!(
+                    "`presence_mask` supports at most 64 non-skipped fields, found {}",
+                    kept.len()
+                ),
+            )
+            .to_compile_error();
+        }
+
+        let bits = kept.iter().enumerate().map(|(bit, f)| {
+            let name = field_member(s, f);
+            let bit = bit as u32;
+            let cfgs = cfg_attrs(&f.attrs);
+            let field_opts = opts.field_opts(s, f);
+            if is_option_type(&f.ty).is_some() {
+                quote! { #(#cfgs)* { mask |= u64::from(original.#name.is_some()) << #bit; } }
+            } else if boxed_option_field(s, f, &field_opts, &proc_usage_opts).is_some() {
+                quote! { #(#cfgs)* { mask |= u64::from((*original.#name).is_some()) << #bit; } }
+            } else {
+                quote! { #(#cfgs)* { mask |= 1 << #bit; } }
+            }
+        });
+
+        quote! {
+            impl #impl_generics #unwrapped_ident #mirror_ty_generics #where_clause {
+                /// Bitmask of the populated fields of `original`.
+                ///
+                /// Bit `i` belongs to the `i`-th non-skipped field in declaration order. It is set when an
+                /// `Option` or `Box<Option<T>>` field is `Some`, and always set for other fields.
+                #[allow(unused_mut, unused_variables)]
+                pub fn presence_mask(original: &#original_ident #ty_generics) -> u64 {
+                    let mut mask = 0u64;
+                    #(#bits)*
+                    mask
+                }
+            }
+        }
+    } else {
+        quote! {}
+    };
+
+    // The type a non-skipped field has on the generated struct
+    let generated_ty = |f: &syn::Field, field_opts: &FieldOpts| {
+        if let Some(inner_ty) = recursive_field(s, f, field_opts, &proc_usage_opts) {
+            quote! { <#inner_ty as #lib_path::Unwrapped>::Unwrapped }
+        } else if let Some(inner_ty) = boxed_option_field(s, f, field_opts, &proc_usage_opts) {
+            quote! { ::std::boxed::Box<#inner_ty> }
+        } else if unwraps_field(s, f, field_opts, &proc_usage_opts) {
+            let inner_ty = is_option_type(&f.ty).unwrap();
+            quote! { #inner_ty }
+        } else if let Some(OptionLike { inner_ty, .. }) =
+            option_like_field(s, f, field_opts, &proc_usage_opts)
+        {
+            quote! { #inner_ty }
+        } else {
+            let ty = &f.ty;
+            quote! { #ty }
+        }
+    };
+
+    // Generate a manual Default - fields without an explicit `default` must implement `Default`
+    // Only the syntax is visible here, so an unrecognised type needs an explicit default
+    if opts.smart_default {
+        for f in &s.fields {
+            let field_opts = opts.field_opts(s, f);
+            if field_opts.skip || field_opts.default.is_some() {
+                continue;
+            }
+            let known = if recursive_field(s, f, &field_opts, &proc_usage_opts).is_some() {
+                false
+            } else if let Some(inner_ty) = boxed_option_field(s, f, &field_opts, &proc_usage_opts) {
+                is_known_default_type(inner_ty)
+            } else if unwraps_field(s, f, &field_opts, &proc_usage_opts) {
+                is_known_default_type(is_option_type(&f.ty).unwrap())
+            } else if let Some(OptionLike { inner_ty, .. }) =
+                option_like_field(s, f, &field_opts, &proc_usage_opts)
+            {
+                is_known_default_type(inner_ty)
+            } else {
+                is_known_default_type(&f.ty)
+            };
+            if !known {
+                return syn::Error::new_spanned(
+                    &f.ty,
+                    "`smart_default` cannot tell that this type implements `Default`, give the field `#[unwrapped(default = ...)]`",
+                )
+                .to_compile_error();
+            }
+        }
+    }
+
+    let default_impl = if opts.auto_default || opts.smart_default {
+        let default_fields = gated_fields(&s.fields, |f| {
+            let field_opts = opts.field_opts(s, f);
+            if field_opts.skip {
+                return None;
+            }
+
+            let uw_name = opts.generated_member(s, f);
+            let generated_ty = generated_ty(f, &field_opts);
+
+            Some(match &field_opts.default {
+                Some(default) => quote! { #uw_name: #default },
+                // Spanned so a missing `Default` impl points at the field type
+                None => quote_spanned! { f.ty.span()=>
+                    #uw_name: <#generated_ty as ::core::default::Default>::default()
+                },
+            })
+        });
+
+        quote! {
+            impl #mirror_impl_generics ::core::default::Default for #unwrapped_ident #mirror_ty_generics #mirror_where_clause {
+                fn default() -> Self {
+                    Self {
+                        #(#default_fields),*
+                    }
+                }
+            }
+        }
+    } else {
+        quote! {}
+    };
+
+    // Generate sample - without the `testing` feature nothing is emitted, so production builds
+    // never carry the fixture
+    let sample_helper = if opts.sample && cfg!(feature = "testing") {
+        let sample_fields = gated_fields(&s.fields, |f| {
+            let field_opts = opts.field_opts(s, f);
+            if field_opts.skip {
+                return None;
+            }
+
+            let uw_name = opts.generated_member(s, f);
+            let generated_ty = generated_ty(f, &field_opts);
+            Some(
+                match field_opts.sample.as_ref().or(field_opts.default.as_ref()) {
+                    Some(sample) => quote! { #uw_name: #sample },
+                    // Spanned so a missing `Default` impl points at the field type
+                    None => quote_spanned! { f.ty.span()=>
+                        #uw_name: <#generated_ty as ::core::default::Default>::default()
+                    },
+                },
+            )
+        });
+
+        quote! {
+            impl #mirror_impl_generics #unwrapped_ident #mirror_ty_generics #mirror_where_clause {
+                /// A fixed value for tests, with each field set to its `sample`, its `default` or
+                /// `Default::default()`.
+                pub fn sample() -> Self {
+                    Self {
+                        #(#sample_fields),*
+                    }
+                }
+            }
+        }
+    } else {
+        quote! {}
+    };
+
+    // Generate builder - every setter stores its value, `build` panics on a field that was never set
+    let uw_builder_helper = if opts.builder {
+        let builder_ident = format_ident!("{}Builder", unwrapped_ident);
+        let builder_fields = gated_fields(&s.fields, |f| {
+            let field_opts = opts.field_opts(s, f);
+            let name = opts.generated_member(s, f);
+            let generated_ty = generated_ty(f, &field_opts);
+            (!field_opts.skip).then(|| quote! { #name: ::core::option::Option<#generated_ty> })
+        });
+        let empty_fields = gated_fields(&s.fields, |f| {
+            let name = opts.generated_member(s, f);
+            (!opts.field_opts(s, f).skip).then(|| quote! { #name: ::core::option::Option::None })
+        });
+        let setters = gated_fields(&s.fields, |f| {
+            let field_opts = opts.field_opts(s, f);
+            let name = opts.generated_member(s, f);
+            let generated_ty = generated_ty(f, &field_opts);
+            (!field_opts.skip).then(|| {
+                quote! {
+                    pub fn #name(mut self, value: impl ::core::convert::Into<#generated_ty>) -> Self {
+                        self.#name = ::core::option::Option::Some(value.into());
+                        self
+                    }
+                }
+            })
+        });
+        let build_fields = gated_fields(&s.fields, |f| {
+            if opts.field_opts(s, f).skip {
+                return None;
+            }
+            let syn::Member::Named(name) = opts.generated_member(s, f) else {
+                unreachable!("Expected named field");
+            };
+            let message = format!(
+                "`{}` must be set before calling `build`",
+                raw_ident_name(&name)
+            );
+            Some(quote! { #name: self.#name.expect(#message) })
+        });
+
+        quote! {
+            /// Builder for the generated struct, returned by its `builder()` method.
+            #vis struct #builder_ident #mirror_impl_generics #mirror_where_clause {
+                #(#builder_fields,)*
+            }
+
+            impl #mirror_impl_generics ::core::default::Default for #builder_ident #mirror_ty_generics #mirror_where_clause {
+                fn default() -> Self {
+                    Self {
+                        #(#empty_fields,)*
+                    }
+                }
+            }
+
+            impl #mirror_impl_generics #unwrapped_ident #mirror_ty_generics #mirror_where_clause {
+                /// Start building the struct one field at a time.
+                pub fn builder() -> #builder_ident #mirror_ty_generics {
+                    ::core::default::Default::default()
+                }
+            }
+
+            impl #mirror_impl_generics #builder_ident #mirror_ty_generics #mirror_where_clause {
+                #(#setters)*
+
+                /// Finish building.
+                ///
+                /// # Panics
+                ///
+                /// Panics if a field was never set.
+                pub fn build(self) -> #unwrapped_ident #mirror_ty_generics {
+                    #unwrapped_ident {
+                        #(#build_fields,)*
+                    }
+                }
+            }
+        }
+    } else {
+        quote! {}
+    };
+
+    let bridge = if opts.bridge {
+        match bridge_impls(input, &opts, unwrapped_ident, &lib_path, &proc_usage_opts) {
+            Ok(bridge) => bridge,
+            Err(err) => return err.to_compile_error(),
+        }
+    } else {
+        quote! {}
+    };
+
+    let mut struct_derives = opts.struct_derives.clone();
+    if opts.arbitrary {
+        if !cfg!(feature = "proptest") {
+            return syn::Error::new_spanned(
+                original_ident,
+                "`arbitrary` requires the `proptest` feature of `unwrapped`",
+            )
+            .to_compile_error();
+        }
+        struct_derives.push(quote! { ::proptest_derive::Arbitrary });
+    }
+    if opts.zeroize {
+        if !cfg!(feature = "zeroize") {
+            return syn::Error::new_spanned(
+                original_ident,
+                "`zeroize` requires the `zeroize` feature of `unwrapped`",
+            )
+            .to_compile_error();
+        }
+        struct_derives.push(quote! { ::zeroize::Zeroize });
+    }
+
+    // Generate as_original - only sound when both structs are `#[repr(C)]` with identical fields
+    let mut struct_attrs = opts.struct_attrs.clone();
+    if !common_opts.skips_attr("cfg_attr") {
+        struct_attrs.extend(forwarded_test_attrs(
+            &input.attrs,
+            derives_serde(&struct_derives),
+        ));
+    }
+    // Helper attributes of the generated derives, emitted after `#[derive(...)]`
+    let mut derive_helper_attrs = forwarded_container_attrs(&input.attrs, &common_opts);
+    let derives_builder = struct_derives.iter().any(|tokens| {
+        tokens.clone().into_iter().any(
+            |token| matches!(token, proc_macro2::TokenTree::Ident(ident) if ident == "Builder"),
+        )
+    });
+    // bon names its builder `{Uw}Builder` too, so the two would collide
+    if opts.builder && derives_builder {
+        return syn::Error::new_spanned(
+            original_ident,
+            format!(
+                "`builder` generates `{}Builder`, which collides with the builder of the forwarded `derive(Builder)`; use only one of them",
+                unwrapped_ident
+            ),
+        )
+        .to_compile_error();
+    }
+    if let Some(state_mod) = &opts.builder_state_mod {
+        if !derives_builder {
+            return syn::Error::new_spanned(
+                state_mod,
+                "`builder_state_mod` requires the generated struct to derive `Builder`",
+            )
+            .to_compile_error();
+        }
+        derive_helper_attrs.push(quote! { #[builder(state_mod = #state_mod)] });
+    }
+    let reinterpret_helper = if opts.reinterpret {
+        if !has_repr_c(&input.attrs) {
+            return syn::Error::new_spanned(
+                original_ident,
+                "`reinterpret` requires `#[repr(C)]` on the input struct",
+            )
+            .to_compile_error();
+        }
+        if let Some(f) = s.fields.iter().find(|f| {
+            let field_opts = opts.field_opts(s, f);
+            field_opts.skip
+                || unwraps_field(s, f, &field_opts, &proc_usage_opts)
+                || boxed_option_field(s, f, &field_opts, &proc_usage_opts).is_some()
+                || option_like_field(s, f, &field_opts, &proc_usage_opts).is_some()
+        }) {
+            return syn::Error::new_spanned(
+                f,
+                "`reinterpret` requires every field to be kept unchanged, this field is skipped or unwrapped",
+            )
+            .to_compile_error();
+        }
+        struct_attrs.push(quote! { #[repr(C)] });
+
+        quote! {
+            impl #impl_generics #unwrapped_ident #mirror_ty_generics #where_clause {
+                const __UNWRAPPED_LAYOUT_CHECK: () = assert!(
+                    ::core::mem::size_of::<Self>() == ::core::mem::size_of::<#original_ident #ty_generics>()
+                        && ::core::mem::align_of::<Self>() == ::core::mem::align_of::<#original_ident #ty_generics>(),
+                    "layouts of the generated and original structs differ",
+                );
+
+                /// View this struct as the original struct without copying.
+                pub fn as_original(&self) -> &#original_ident #ty_generics {
+                    #[allow(clippy::let_unit_value)]
+                    let () = Self::__UNWRAPPED_LAYOUT_CHECK;
+                    // SAFETY: both structs are `#[repr(C)]` and declare the same field types in the
+                    // same order, so they have the same layout, which the check above also asserts.
+                    unsafe { &*(self as *const Self).cast::<#original_ident #ty_generics>() }
+                }
+            }
+        }
+    } else {
+        quote! {}
+    };
+
+    // Metadata for the trait impl
+    let field_count = field_count(s.fields.iter().filter(|f| !opts.field_opts(s, f).skip));
+    let source_name = original_ident.to_string();
+
+    // Build struct-level attributes and derives
+    let struct_attrs = &struct_attrs;
+    let derive_output = build_derive_output(&struct_derives);
+    let float_warnings = float_derive_warnings(
+        &struct_derives,
+        s.fields
+            .iter()
+            .filter(|f| !opts.field_opts(s, f).skip)
+            .map(|f| (field_key(s, f), &f.ty)),
+    );
+    let struct_body = match &s.fields {
+        syn::Fields::Unnamed(_) => quote! { (#(#fields),*) #mirror_where_clause; },
+        _ => quote! { #mirror_where_clause { #(#fields),* } },
+    };
+
+    // The generated struct and public helper types are emitted in the caller's module, every impl
+    // goes in an anonymous `const _` block so helper items never collide across derives
+    //
+    // Only generate From implementations if there are no skipped fields
+    if has_skipped_fields {
+        // Collect skipped fields for into_original method
+        let skipped_params = gated_fields(&s.fields, |f| {
+            let field_opts = opts.field_opts(s, f);
+            if field_opts.skip && field_opts.default.is_none() {
+                let param = skipped_param(s, f);
+                let ty = &f.ty;
+                Some(quote! { #param: #ty })
+            } else {
+                None
+            }
+        });
+
+        // Build field assignments for into_original
+        let into_original_fields = gated_fields(&s.fields, |f| {
+            let field_opts = opts.field_opts(s, f);
+            let name = field_member(s, f);
+            let uw_name = opts.generated_member(s, f);
+
+            if let (true, Some(default)) = (field_opts.skip, &field_opts.default) {
+                quote! { #name: #default }
+            } else if field_opts.skip {
+                // Skipped fields come from parameters
+                let param = skipped_param(s, f);
+                quote! { #name: #param }
+            } else if recursive_field(s, f, &field_opts, &proc_usage_opts).is_some() {
+                quote! { #name: Some(::core::convert::From::from(self.#uw_name)) }
+            } else if boxed_option_field(s, f, &field_opts, &proc_usage_opts).is_some() {
+                quote! { #name: ::std::boxed::Box::new(Some(*self.#uw_name)) }
+            } else if unwraps_field(s, f, &field_opts, &proc_usage_opts) {
+                // Non-skipped Option fields that were unwrapped -> wrap them back
+                quote! { #name: Some(self.#uw_name) }
+            } else if let Some(option_like) = option_like_field(s, f, &field_opts, &proc_usage_opts)
+            {
+                let value = option_like.convert_back(quote! { self.#uw_name });
+                quote! { #name: #value }
+            } else {
+                // Non-skipped non-Option fields
+                quote! { #name: self.#uw_name }
+            }
+        });
+
+        // Skipped fields fall back to their defaults when converting without `into_original`, which
+        // needs no opt-in when every skipped field has its own `default`
+        let every_skip_defaults = s.fields.iter().all(|f| {
+            let field_opts = opts.field_opts(s, f);
+            !field_opts.skip || field_opts.default.is_some()
+        });
+        let skip_default_from = if opts.skip_default || every_skip_defaults {
+            let from_fields = gated_fields(&s.fields, |f| {
+                let field_opts = opts.field_opts(s, f);
+                let name = field_member(s, f);
+                let uw_name = opts.generated_member(s, f);
+
+                if let (true, Some(default)) = (field_opts.skip, &field_opts.default) {
+                    quote! { #name: #default }
+                } else if field_opts.skip {
+                    quote! { #name: ::core::default::Default::default() }
+                } else if recursive_field(s, f, &field_opts, &proc_usage_opts).is_some() {
+                    quote! { #name: Some(::core::convert::From::from(from.#uw_name)) }
+                } else if boxed_option_field(s, f, &field_opts, &proc_usage_opts).is_some() {
+                    quote! { #name: ::std::boxed::Box::new(Some(*from.#uw_name)) }
+                } else if unwraps_field(s, f, &field_opts, &proc_usage_opts) {
+                    quote! { #name: Some(from.#uw_name) }
+                } else if let Some(option_like) =
+                    option_like_field(s, f, &field_opts, &proc_usage_opts)
+                {
+                    let value = option_like.convert_back(quote! { from.#uw_name });
+                    quote! { #name: #value }
+                } else {
+                    quote! { #name: from.#uw_name }
+                }
+            });
+
+            quote! {
+                impl #impl_generics ::core::convert::From<#unwrapped_ident #mirror_ty_generics> for #original_ident #ty_generics #where_clause {
+                    fn from(from: #unwrapped_ident #mirror_ty_generics) -> Self {
+                        Self {
+                            #(#from_fields),*
+                        }
+                    }
+                }
+            }
+        } else {
+            quote! {}
+        };
+
+        // Skipped fields are cloned from a fallback instance, every other field comes from `self`
+        let into_original_or_helper = if opts.into_original_or {
+            let or_fields = gated_fields(&s.fields, |f| {
+                let field_opts = opts.field_opts(s, f);
+                let name = field_member(s, f);
+                let uw_name = opts.generated_member(s, f);
+
+                if field_opts.skip {
+                    quote! { #name: ::core::clone::Clone::clone(&fallback.#name) }
+                } else if recursive_field(s, f, &field_opts, &proc_usage_opts).is_some() {
+                    quote! { #name: Some(::core::convert::From::from(self.#uw_name)) }
+                } else if boxed_option_field(s, f, &field_opts, &proc_usage_opts).is_some() {
+                    quote! { #name: ::std::boxed::Box::new(Some(*self.#uw_name)) }
+                } else if unwraps_field(s, f, &field_opts, &proc_usage_opts) {
+                    quote! { #name: Some(self.#uw_name) }
+                } else if let Some(option_like) =
+                    option_like_field(s, f, &field_opts, &proc_usage_opts)
+                {
+                    let value = option_like.convert_back(quote! { self.#uw_name });
+                    quote! { #name: #value }
+                } else {
+                    quote! { #name: self.#uw_name }
+                }
+            });
+
+            quote! {
+                impl #impl_generics #unwrapped_ident #mirror_ty_generics #where_clause {
+                    /// Convert back to the original struct, cloning skipped fields from `fallback`.
+                    ///
+                    /// Fields present in `self` always win over `fallback`.
+                    pub fn into_original_or(self, fallback: &#original_ident #ty_generics) -> #original_ident #ty_generics {
+                        #original_ident {
+                            #(#or_fields),*
+                        }
+                    }
+                }
+            }
+        } else {
+            quote! {}
+        };
+
+        // bon only derives builders for structs with named fields, and a setter chain can't leave
+        // out a field behind `#[cfg(...)]`
+        let builder_info = match &s.fields {
+            syn::Fields::Named(_)
+                if s.fields
+                    .iter()
+                    .all(|f| opts.field_opts(s, f).skip || cfg_attrs(&f.attrs).is_empty()) =>
+            {
+                bon_builder_info(input)
+            },
+            _ => None,
+        };
+        let builder_helper = if let Some(builder_info) = builder_info {
+            let builder_ident = &builder_info.builder_ident;
+            let state_mod_ident = &builder_info.state_mod_ident;
+            let state_ident = unique_state_ident(&input.generics);
 
             let mut builder_generics = input.generics.clone();
             builder_generics
@@ -349,35 +2820,55 @@ pub fn unwrapped(
             let mut state_bounds = Vec::new();
 
             for f in s.fields.iter() {
-                let field_opts = FieldOpts::from_field(f).expect("Wrong field options");
+                let field_opts = opts.field_opts(s, f);
                 if field_opts.skip {
                     continue;
                 }
 
                 let name = f.ident.as_ref().expect("Expected named field");
+                let uw_name = opts.generated_member(s, f);
                 let ty = &f.ty;
-                let name_str = name.to_string();
 
-                let (setter_ident, value) = if let syn::Type::Path(p) = ty
-                    && let Some(seg) = p.path.segments.last()
-                    && seg.ident == "Option"
-                {
-                    let should_unwrap = *proc_usage_opts
-                        .fields_to_unwrap
-                        .get(&name_str)
-                        .unwrap_or(&true);
-                    if should_unwrap {
-                        (name.clone(), quote! { uw.#name })
-                    } else {
-                        let maybe_name = syn::Ident::new(
-                            &format!("maybe_{}", raw_ident_name(name)),
-                            name.span(),
+                // Conversions name their target so setters taking `impl Into<T>` (bon's
+                // `on(T, into)`) can still infer it
+                let (setter_ident, value) =
+                    if let Some(inner_ty) = recursive_field(s, f, &field_opts, &proc_usage_opts) {
+                        (
+                            name.clone(),
+                            quote! { <#inner_ty as ::core::convert::From<_>>::from(uw.#uw_name) },
+                        )
+                    } else if boxed_option_field(s, f, &field_opts, &proc_usage_opts).is_some() {
+                        (
+                            name.clone(),
+                            quote! { ::std::boxed::Box::new(Some(*uw.#uw_name)) },
+                        )
+                    } else if let Some(option_like) =
+                        option_like_field(s, f, &field_opts, &proc_usage_opts)
+                    {
+                        // A `with` field may be an `Option` itself, set through `maybe_<field>`
+                        let setter_ident = if is_option_type(ty).is_some() {
+                            syn::Ident::new(&format!("maybe_{}", raw_ident_name(name)), name.span())
+                        } else {
+                            name.clone()
+                        };
+                        let value = quote! { uw.#uw_name };
+                        let value = option_like.back(&value).unwrap_or_else(
+                            || quote! { <#ty as ::core::convert::From<_>>::from(#value) },
                         );
-                        (maybe_name, quote! { uw.#name })
-                    }
-                } else {
-                    (name.clone(), quote! { uw.#name })
-                };
+                        (setter_ident, value)
+                    } else if is_option_type(ty).is_some() {
+                        if unwraps_field(s, f, &field_opts, &proc_usage_opts) {
+                            (name.clone(), quote! { uw.#uw_name })
+                        } else {
+                            let maybe_name = syn::Ident::new(
+                                &format!("maybe_{}", raw_ident_name(name)),
+                                name.span(),
+                            );
+                            (maybe_name, quote! { uw.#uw_name })
+                        }
+                    } else {
+                        (name.clone(), quote! { uw.#uw_name })
+                    };
 
                 setter_calls.push(quote! { .#setter_ident(#value) });
 
@@ -408,7 +2899,7 @@ pub fn unwrapped(
             quote! {
                 impl #builder_impl_generics #builder_ident #builder_ty_generics #builder_where_clause {
                     /// Pre-fill the builder with the non-skipped fields from the unwrapped struct.
-                    pub fn from_unwrapped(self, uw: #unwrapped_ident #ty_generics) -> #builder_return_ty
+                    pub fn from_unwrapped(self, uw: #unwrapped_ident #mirror_ty_generics) -> #builder_return_ty
                     #method_where
                     {
                         self #(#setter_calls)*
@@ -422,68 +2913,202 @@ pub fn unwrapped(
         quote! {
             #(#struct_attrs)*
             #derive_output
-            pub struct #unwrapped_ident #ty_generics #where_clause {
-                #(#fields),*
-            }
+            #(#derive_helper_attrs)*
+            #vis struct #unwrapped_ident #mirror_impl_generics #struct_body
 
-            impl #impl_generics ::#lib_path::Unwrapped for #original_ident #ty_generics #where_clause {
-                type Unwrapped = #unwrapped_ident #ty_generics;
-            }
+            #float_warnings
 
-            impl #impl_generics #unwrapped_ident #ty_generics #where_clause {
-                pub fn try_from(from: #original_ident #ty_generics) -> Result<Self, ::#lib_path::UnwrappedError> {
-                    Ok(Self {
-                        #(#try_from_fields),*
-                    })
+            #view_helper
+
+            #uw_builder_helper
+
+            const _: () = {
+                impl #impl_generics #lib_path::Unwrapped for #original_ident #ty_generics #where_clause {
+                    type Unwrapped = #unwrapped_ident #mirror_ty_generics;
+
+                    const FIELD_COUNT: usize = #field_count;
+                    const SOURCE: &'static str = #source_name;
                 }
 
-                /// Convert back to the original struct by providing values for skipped fields.
-                ///
-                /// This method takes the skipped fields as parameters and reconstructs
-                /// the original struct with non-skipped fields from `self`.
-                ///
-                /// # Example
-                ///
-                /// ```ignore
-                /// let form = UserFormUw { name: "Alice".to_string(), email: "alice@example.com".to_string() };
-                /// let original = form.into_original(1234567890, 42);
-                /// ```
-                pub fn into_original(self, #(#skipped_params),*) -> #original_ident #ty_generics {
-                    #original_ident {
-                        #(#into_original_fields),*
+                impl #impl_generics #unwrapped_ident #mirror_ty_generics #where_clause {
+                    pub fn #try_from_ident(from: #original_ident #ty_generics) -> ::core::result::Result<Self, #lib_path::UnwrappedError> {
+                        #debug_snapshot
+                        #(#metrics_calls)*
+                        #(#tag_checks)*
+
+                        let unwrapped = Self {
+                            #(#try_from_fields),*
+                        };
+                        #debug_check
+
+                        Ok(unwrapped)
+                    }
+
+                    /// Convert back to the original struct by providing values for skipped fields.
+                    ///
+                    /// This method takes the skipped fields as parameters and reconstructs
+                    /// the original struct with non-skipped fields from `self`.
+                    ///
+                    /// # Example
+                    ///
+                    /// ```ignore
+                    /// let form = UserFormUw { name: "Alice".to_string(), email: "alice@example.com".to_string() };
+                    /// let original = form.into_original(1234567890, 42);
+                    /// ```
+                    pub fn into_original(self, #(#skipped_params),*) -> #original_ident #ty_generics {
+                        #original_ident {
+                            #(#into_original_fields),*
+                        }
                     }
                 }
-            }
 
-            #builder_helper
+                #skip_default_from
+
+                #into_original_or_helper
+
+                #builder_helper
+
+                #string_visitor
+
+                #downcast_helper
+
+                #debug_map_helper
+
+                #expect_helper
+
+                #split_helper
+
+                #borrow_helper
+
+                #analyze_helper
+
+                #missing_fields_helper
+
+                #default_impl
+
+                #counted_helper
+
+                #try_from_into_helper
+
+                #try_from_all_helper
+
+                #try_from_partial_helper
+
+                #try_from_trait_impl
+
+                #default_from_impl
+
+                #bench_helper
+
+                #from_into_helper
+
+                #boxed_ctor_helper
+
+                #sample_helper
+
+                #reinterpret_helper
+
+                #presence_mask_helper
+
+                #bridge
+            };
         }
     } else {
         quote! {
             #(#struct_attrs)*
             #derive_output
-            pub struct #unwrapped_ident #ty_generics #where_clause {
-                #(#fields),*
-            }
+            #(#derive_helper_attrs)*
+            #vis struct #unwrapped_ident #mirror_impl_generics #struct_body
 
-            impl #impl_generics From<#unwrapped_ident #ty_generics> for #original_ident #ty_generics #where_clause {
-                fn from(from: #unwrapped_ident #ty_generics) -> Self {
-                    Self {
-                        #(#from_fields),*
+            #float_warnings
+
+            #view_helper
+
+            #uw_builder_helper
+
+            const _: () = {
+                impl #impl_generics ::core::convert::From<#unwrapped_ident #mirror_ty_generics> for #original_ident #ty_generics #where_clause {
+                    fn from(from: #unwrapped_ident #mirror_ty_generics) -> Self {
+                        Self {
+                            #(#from_fields),*
+                        }
                     }
                 }
-            }
 
-            impl #impl_generics ::#lib_path::Unwrapped for #original_ident #ty_generics #where_clause {
-                type Unwrapped = #unwrapped_ident #ty_generics;
-            }
+                impl #impl_generics #lib_path::Unwrapped for #original_ident #ty_generics #where_clause {
+                    type Unwrapped = #unwrapped_ident #mirror_ty_generics;
 
-            impl #impl_generics #unwrapped_ident #ty_generics #where_clause {
-                pub fn try_from(from: #original_ident #ty_generics) -> Result<Self, ::#lib_path::UnwrappedError> {
-                    Ok(Self {
-                        #(#try_from_fields),*
-                    })
+                    const FIELD_COUNT: usize = #field_count;
+                    const SOURCE: &'static str = #source_name;
                 }
-            }
+
+                impl #impl_generics #unwrapped_ident #mirror_ty_generics #where_clause {
+                    pub fn #try_from_ident(from: #original_ident #ty_generics) -> ::core::result::Result<Self, #lib_path::UnwrappedError> {
+                        #debug_snapshot
+                        #(#metrics_calls)*
+                        #(#tag_checks)*
+
+                        let unwrapped = Self {
+                            #(#try_from_fields),*
+                        };
+                        #debug_check
+
+                        Ok(unwrapped)
+                    }
+
+                    /// Convert back to the original struct, the same as its `From` impl.
+                    ///
+                    /// No field is skipped, so this takes no parameters. It matches the
+                    /// `into_original` generated when fields are skipped.
+                    pub fn into_original(self) -> #original_ident #ty_generics {
+                        ::core::convert::From::from(self)
+                    }
+                }
+
+                #string_visitor
+
+                #downcast_helper
+
+                #debug_map_helper
+
+                #expect_helper
+
+                #split_helper
+
+                #borrow_helper
+
+                #analyze_helper
+
+                #missing_fields_helper
+
+                #default_impl
+
+                #counted_helper
+
+                #try_from_into_helper
+
+                #try_from_all_helper
+
+                #try_from_partial_helper
+
+                #try_from_trait_impl
+
+                #default_from_impl
+
+                #bench_helper
+
+                #from_into_helper
+
+                #boxed_ctor_helper
+
+                #sample_helper
+
+                #reinterpret_helper
+
+                #presence_mask_helper
+
+                #bridge
+            };
         }
     }
 }
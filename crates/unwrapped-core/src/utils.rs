@@ -1,8 +1,9 @@
 use std::collections::{HashMap, HashSet};
 
 use ident_case::RenameRule;
-use quote::{format_ident, quote};
+use quote::{format_ident, quote, quote_spanned};
 use syn::parse::Parser as _;
+use syn::spanned::Spanned as _;
 use syn::{DeriveInput, Expr, GenericParam, Meta, Path};
 
 /// Check if a type is `Option<T>` and return the inner type if so
@@ -18,6 +19,134 @@ pub fn is_option_type(ty: &syn::Type) -> Option<&syn::Type> {
     None
 }
 
+/// Check if a type is `Vec<T>` and return the element type if so
+pub fn is_vec_type(ty: &syn::Type) -> Option<&syn::Type> {
+    if let syn::Type::Path(p) = ty
+        && let Some(seg) = p.path.segments.last()
+        && seg.ident == "Vec"
+        && let syn::PathArguments::AngleBracketed(args) = &seg.arguments
+        && let Some(syn::GenericArgument::Type(inner_ty)) = args.args.first()
+    {
+        return Some(inner_ty);
+    }
+    None
+}
+
+/// Check if a type is `Box<Option<T>>` and return the inner `T` if so
+pub fn is_boxed_option_type(ty: &syn::Type) -> Option<&syn::Type> {
+    if let syn::Type::Path(p) = ty
+        && let Some(seg) = p.path.segments.last()
+        && seg.ident == "Box"
+        && let syn::PathArguments::AngleBracketed(args) = &seg.arguments
+        && let Some(syn::GenericArgument::Type(inner_ty)) = args.args.first()
+    {
+        return is_option_type(inner_ty);
+    }
+    None
+}
+
+/// Check if a type is a plain `String`
+pub fn is_string_type(ty: &syn::Type) -> bool {
+    if let syn::Type::Path(p) = ty
+        && p.qself.is_none()
+        && let Some(seg) = p.path.segments.last()
+    {
+        return seg.ident == "String" && seg.arguments.is_none();
+    }
+    false
+}
+
+/// Whether a type is known to implement `Default` from its syntax alone: primitives, `String`,
+/// `&str`, std collections, and tuples, arrays and smart pointers of known types
+pub(crate) fn is_known_default_type(ty: &syn::Type) -> bool {
+    match ty {
+        syn::Type::Paren(paren) => is_known_default_type(&paren.elem),
+        syn::Type::Group(group) => is_known_default_type(&group.elem),
+        syn::Type::Tuple(tuple) => tuple.elems.iter().all(is_known_default_type),
+        syn::Type::Array(array) => is_known_default_type(&array.elem),
+        syn::Type::Reference(reference) => {
+            reference.mutability.is_none()
+                && matches!(&*reference.elem, syn::Type::Path(p) if p.path.is_ident("str"))
+        },
+        syn::Type::Path(p) if p.qself.is_none() => {
+            let Some(seg) = p.path.segments.last() else {
+                return false;
+            };
+            let args: Vec<&syn::Type> = match &seg.arguments {
+                syn::PathArguments::None => Vec::new(),
+                syn::PathArguments::AngleBracketed(args) => args
+                    .args
+                    .iter()
+                    .filter_map(|arg| match arg {
+                        syn::GenericArgument::Type(ty) => Some(ty),
+                        _ => None,
+                    })
+                    .collect(),
+                syn::PathArguments::Parenthesized(_) => return false,
+            };
+            match seg.ident.to_string().as_str() {
+                "bool" | "char" | "u8" | "u16" | "u32" | "u64" | "u128" | "usize" | "i8"
+                | "i16" | "i32" | "i64" | "i128" | "isize" | "f32" | "f64" | "String" => {
+                    args.is_empty()
+                },
+                // Empty whatever the element types are
+                "Option" | "Vec" | "VecDeque" | "LinkedList" | "BinaryHeap" | "HashMap"
+                | "HashSet" | "BTreeMap" | "BTreeSet" | "PhantomData" => true,
+                "Box" | "Rc" | "Arc" | "Cell" | "RefCell" | "Mutex" | "RwLock" => {
+                    args.len() == 1 && is_known_default_type(args[0])
+                },
+                _ => false,
+            }
+        },
+        _ => false,
+    }
+}
+
+/// Check if a type is a `Box`, `Rc` or `Arc` of a `dyn Any` trait object, optionally `+ Send`
+/// and `+ Sync`, so `downcast_ref` is reachable through it
+pub(crate) fn is_dyn_any_pointer(ty: &syn::Type) -> bool {
+    if let syn::Type::Path(p) = ty
+        && p.qself.is_none()
+        && let Some(seg) = p.path.segments.last()
+        && matches!(seg.ident.to_string().as_str(), "Box" | "Rc" | "Arc")
+        && let syn::PathArguments::AngleBracketed(args) = &seg.arguments
+        && let Some(syn::GenericArgument::Type(syn::Type::TraitObject(object))) = args.args.first()
+    {
+        let traits: Vec<String> = object
+            .bounds
+            .iter()
+            .filter_map(|bound| match bound {
+                syn::TypeParamBound::Trait(bound) => {
+                    bound.path.segments.last().map(|seg| seg.ident.to_string())
+                },
+                _ => None,
+            })
+            .collect();
+        return traits.iter().any(|name| name == "Any")
+            && traits
+                .iter()
+                .all(|name| matches!(name.as_str(), "Any" | "Send" | "Sync"));
+    }
+    false
+}
+
+/// The input with extra `where` predicates merged into its generics, so every `split_for_impl`
+/// and mirror of the generics carries them
+pub(crate) fn with_where_predicates(
+    input: &DeriveInput,
+    predicates: &[syn::WherePredicate],
+) -> DeriveInput {
+    let mut input = input.clone();
+    if !predicates.is_empty() {
+        input
+            .generics
+            .make_where_clause()
+            .predicates
+            .extend(predicates.iter().cloned());
+    }
+    input
+}
+
 /// Extract the struct data from a DeriveInput, panicking if it's not a struct
 pub fn get_struct_data(input: &DeriveInput) -> &syn::DataStruct {
     if let syn::Data::Struct(s) = &input.data {
@@ -27,6 +156,30 @@ pub fn get_struct_data(input: &DeriveInput) -> &syn::DataStruct {
     }
 }
 
+/// Position of a field within its struct
+pub(crate) fn field_position(s: &syn::DataStruct, f: &syn::Field) -> usize {
+    s.fields
+        .iter()
+        .position(|other| std::ptr::eq(other, f))
+        .expect("Expected a field of the struct")
+}
+
+/// Key of a field in name-based lookups: its name, or its position for a tuple struct field
+pub(crate) fn field_key(s: &syn::DataStruct, f: &syn::Field) -> String {
+    match &f.ident {
+        Some(ident) => ident.to_string(),
+        None => field_position(s, f).to_string(),
+    }
+}
+
+/// How a field is accessed on the input struct: by name, or by position for a tuple struct field
+pub(crate) fn field_member(s: &syn::DataStruct, f: &syn::Field) -> syn::Member {
+    match &f.ident {
+        Some(ident) => syn::Member::Named(ident.clone()),
+        None => syn::Member::Unnamed(field_position(s, f).into()),
+    }
+}
+
 /// Common options struct for both Unwrapped and Wrapped
 #[derive(Clone, Debug, Default)]
 pub struct CommonOpts {
@@ -36,6 +189,10 @@ pub struct CommonOpts {
     pub struct_derives: Vec<proc_macro2::TokenStream>,
     pub struct_attrs: Vec<proc_macro2::TokenStream>,
     pub field_attrs: HashMap<String, Vec<proc_macro2::TokenStream>>,
+    /// Attribute namespaces copied verbatim from the input, on top of the default ones
+    pub forward_attrs: Vec<String>,
+    /// Attribute namespaces never copied from the input, including the default ones
+    pub skip_attrs: Vec<String>,
 }
 
 impl CommonOpts {
@@ -61,6 +218,20 @@ impl CommonOpts {
         }
     }
 
+    /// Whether input attributes in `namespace` are dropped from the generated struct
+    pub fn skips_attr(&self, namespace: &str) -> bool {
+        self.skip_attrs.iter().any(|skipped| skipped == namespace)
+    }
+
+    /// Whether input attributes in `namespace` are copied verbatim to the generated struct
+    pub fn forwards_attr(&self, namespace: &str) -> bool {
+        !self.skips_attr(namespace)
+            && self
+                .forward_attrs
+                .iter()
+                .any(|forwarded| forwarded == namespace)
+    }
+
     /// Add a derive to the generated struct
     pub fn with_derive(mut self, tokens: impl Into<proc_macro2::TokenStream>) -> Self {
         self.struct_derives.push(tokens.into());
@@ -115,34 +286,50 @@ impl FieldProcOpts {
     }
 }
 
+/// A field of the input struct as seen by `with_field_attr_fn_ctx`
+#[derive(Clone, Copy, Debug)]
+pub struct FieldContext<'a> {
+    pub field: &'a syn::Field,
+    /// Zero-based position of the field in the input struct, counting skipped fields
+    pub index: usize,
+    /// Name of the input struct
+    pub struct_ident: &'a syn::Ident,
+    /// Whether the field is unwrapped, or wrapped, rather than copied as is
+    pub transformed: bool,
+}
+
 /// Common procedural usage options
 #[derive(Clone, Debug, Default)]
 pub struct ProcUsageOpts {
     pub fields_to_transform: HashMap<String, bool>,
-    pub lib_holder_name: Option<syn::Ident>,
+    pub lib_holder_name: Option<syn::Path>,
     pub field_opts: HashMap<String, FieldProcOpts>,
     pub field_attr_fn: Option<fn(&syn::Field) -> Option<proc_macro2::TokenStream>>,
+    pub field_attr_fn_ctx: Option<fn(&FieldContext<'_>) -> Option<proc_macro2::TokenStream>>,
 }
 
 impl ProcUsageOpts {
     pub fn new(
         fields_to_transform: HashMap<String, bool>,
-        lib_holder_name: Option<syn::Ident>,
+        lib_holder_name: Option<syn::Path>,
     ) -> Self {
         Self {
             fields_to_transform,
             lib_holder_name,
             field_opts: HashMap::new(),
             field_attr_fn: None,
+            field_attr_fn_ctx: None,
         }
     }
 
     pub fn lib_path(&self) -> syn::Path {
-        if let Some(name) = &self.lib_holder_name {
-            syn::parse_str(&format!("{}::unwrapped", name)).unwrap()
-        } else {
-            syn::parse_str("unwrapped").unwrap()
-        }
+        lib_path(self.lib_holder_name.as_ref())
+    }
+
+    /// Set the crate that re-exports `unwrapped`, or the full path to it, see `lib_path`
+    pub fn with_lib_holder(mut self, name: impl Into<syn::Path>) -> Self {
+        self.lib_holder_name = Some(name.into());
+        self
     }
 
     /// Set options for a specific field
@@ -152,6 +339,18 @@ impl ProcUsageOpts {
         self
     }
 
+    /// Set options for many fields at once, later entries override earlier ones
+    pub fn with_field_opts_bulk<K: AsRef<str>>(
+        mut self,
+        field_opts: impl IntoIterator<Item = (K, FieldProcOpts)>,
+    ) -> Self {
+        for (field_name, opts) in field_opts {
+            self.field_opts
+                .insert(field_name.as_ref().to_string(), opts);
+        }
+        self
+    }
+
     /// Set a dynamic field attribute generator
     pub fn with_field_attr_fn(
         mut self,
@@ -160,16 +359,88 @@ impl ProcUsageOpts {
         self.field_attr_fn = Some(f);
         self
     }
+
+    /// Set a dynamic field attribute generator that also sees the field's position and struct
+    pub fn with_field_attr_fn_ctx(
+        mut self,
+        f: fn(&FieldContext<'_>) -> Option<proc_macro2::TokenStream>,
+    ) -> Self {
+        self.field_attr_fn_ctx = Some(f);
+        self
+    }
+}
+
+/// Absolute path to the `unwrapped` crate, optionally re-exported by `lib_holder_name`.
+///
+/// A single identifier names the crate re-exporting `unwrapped`, while a longer path is used as-is,
+/// e.g. `crate::vendored::uw`. `crate` and `self` resolve to `crate`, for code living inside the
+/// `unwrapped` crate itself.
+pub(crate) fn lib_path(lib_holder_name: Option<&syn::Path>) -> syn::Path {
+    let Some(path) = lib_holder_name else {
+        return syn::parse_str("::unwrapped").unwrap();
+    };
+    match path.get_ident() {
+        Some(name) if name == "crate" || name == "self" => syn::parse_quote!(crate),
+        Some(name) => syn::parse_str(&format!("::{}::unwrapped", name)).unwrap(),
+        None => path.clone(),
+    }
+}
+
+/// An `UnwrappedError` for `field_name` raised by `source`, with `kind` naming an
+/// `UnwrappedErrorKind` variant or constructor, e.g. `quote!(Assertion)`
+pub(crate) fn unwrapped_error(
+    lib_path: &syn::Path,
+    field_name: &str,
+    source: &proc_macro2::TokenStream,
+    kind: proc_macro2::TokenStream,
+) -> proc_macro2::TokenStream {
+    quote! {
+        #lib_path::UnwrappedError {
+            field_name: ::std::borrow::Cow::Borrowed(#field_name),
+            source_field: #source,
+            kind: #lib_path::UnwrappedErrorKind::#kind,
+        }
+    }
+}
+
+/// An `UnwrappedError` for `field_name` being `None`, see `unwrapped_error`
+pub(crate) fn missing_error(
+    lib_path: &syn::Path,
+    field_name: &str,
+    source: &proc_macro2::TokenStream,
+) -> proc_macro2::TokenStream {
+    unwrapped_error(lib_path, field_name, source, quote! { Missing })
 }
 
 /// Collect field attributes from all sources
 pub fn collect_field_attrs(
-    f: &syn::Field,
+    s: &syn::DataStruct,
+    ctx: &FieldContext<'_>,
+    derive_attrs: &[proc_macro2::TokenStream],
     opts: &CommonOpts,
     proc_usage_opts: &ProcUsageOpts,
 ) -> Vec<proc_macro2::TokenStream> {
-    let name_str = f.ident.as_ref().unwrap().to_string();
+    let f = ctx.field;
+    let name_str = field_key(s, f);
     let mut attrs = Vec::new();
+    // Forwarding `serde` verbatim replaces the default filtered copy
+    if !opts.skips_attr("serde") && !opts.forwards_attr("serde") {
+        attrs.extend(forwarded_serde_attrs(f, &opts.struct_derives));
+    }
+    if !opts.skips_attr("cfg_attr") {
+        attrs.extend(forwarded_test_attrs(
+            &f.attrs,
+            derives_serde(&opts.struct_derives),
+        ));
+    }
+    // Doc comments are emitted ahead of every other field attribute by the caller
+    attrs.extend(
+        forwarded_attrs(&f.attrs, opts)
+            .into_iter()
+            .filter(|attr| !attr.path().is_ident("doc"))
+            .map(|attr| quote! { #attr }),
+    );
+    attrs.extend_from_slice(derive_attrs);
 
     // From CommonOpts field_attrs
     if let Some(opts_attrs) = opts.field_attrs.get(&name_str) {
@@ -187,19 +458,441 @@ pub fn collect_field_attrs(
     {
         attrs.push(attr);
     }
+    if let Some(attr_fn) = proc_usage_opts.field_attr_fn_ctx
+        && let Some(attr) = attr_fn(ctx)
+    {
+        attrs.push(attr);
+    }
+
+    attrs
+}
 
+/// The input attributes in a namespace listed by `forward_attrs`, copied verbatim
+pub(crate) fn forwarded_attrs<'a>(
+    attrs: &'a [syn::Attribute],
+    opts: &CommonOpts,
+) -> Vec<&'a syn::Attribute> {
     attrs
+        .iter()
+        .filter(|attr| {
+            attr.path()
+                .get_ident()
+                .is_some_and(|namespace| opts.forwards_attr(&namespace.to_string()))
+        })
+        .collect()
+}
+
+/// `#[serde(...)]` field keys that change how a value is (de)serialized, kept on the generated field
+///
+/// `borrow` keeps zero-copy deserialization working, the borrowed lifetime stays on the generated
+/// struct because the field still uses it.
+const FORWARDED_SERDE_KEYS: &[&str] = &[
+    "with",
+    "serialize_with",
+    "deserialize_with",
+    "borrow",
+    "skip_serializing",
+    "skip_deserializing",
+];
+
+/// `#[serde(...)]` container keys of an enum that rename every variant's fields, kept on the
+/// generated enum so both serialize the same shape
+const FORWARDED_SERDE_ENUM_KEYS: &[&str] = &["rename_all_fields"];
+
+/// Forward the input field's custom serde (de)serializers when the generated struct derives serde
+fn forwarded_serde_attrs(
+    f: &syn::Field,
+    struct_derives: &[proc_macro2::TokenStream],
+) -> Vec<proc_macro2::TokenStream> {
+    if !derives_serde(struct_derives) {
+        return Vec::new();
+    }
+
+    serde_attrs_with_keys(&f.attrs, FORWARDED_SERDE_KEYS)
+}
+
+/// Container attributes copied from the input, emitted after the generated `#[derive(...)]` that
+/// introduces their helper namespaces
+pub(crate) fn forwarded_container_attrs(
+    attrs: &[syn::Attribute],
+    opts: &CommonOpts,
+) -> Vec<proc_macro2::TokenStream> {
+    let mut forwarded = Vec::new();
+    // Forwarding `serde` verbatim replaces the default filtered copy
+    if !opts.skips_attr("serde") && !opts.forwards_attr("serde") {
+        forwarded.extend(forwarded_serde_enum_attrs(attrs, &opts.struct_derives));
+    }
+    forwarded.extend(
+        forwarded_attrs(attrs, opts)
+            .into_iter()
+            .map(|attr| quote! { #attr }),
+    );
+    forwarded
+}
+
+/// Forward the input enum's field renaming when the generated enum derives serde
+fn forwarded_serde_enum_attrs(
+    attrs: &[syn::Attribute],
+    struct_derives: &[proc_macro2::TokenStream],
+) -> Vec<proc_macro2::TokenStream> {
+    if !derives_serde(struct_derives) {
+        return Vec::new();
+    }
+
+    serde_attrs_with_keys(attrs, FORWARDED_SERDE_ENUM_KEYS)
+}
+
+/// The `#[serde(...)]` attributes narrowed down to `keys`, dropping those left empty
+fn serde_attrs_with_keys(attrs: &[syn::Attribute], keys: &[&str]) -> Vec<proc_macro2::TokenStream> {
+    attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("serde"))
+        .filter_map(|attr| {
+            let metas = attr
+                .parse_args_with(
+                    syn::punctuated::Punctuated::<Meta, syn::Token![,]>::parse_terminated,
+                )
+                .ok()?;
+            let kept: Vec<&Meta> = metas
+                .iter()
+                .filter(|meta| keys.iter().any(|key| meta.path().is_ident(key)))
+                .collect();
+            (!kept.is_empty()).then(|| quote! { #[serde(#(#kept),*)] })
+        })
+        .collect()
+}
+
+/// Forward `#[cfg_attr(test, ...)]` attributes so test builds see the same attributes on the
+/// generated struct as on the input.
+///
+/// The `unwrapped`/`wrapped` helper attributes and `Unwrapped`/`Wrapped` derives are dropped, since
+/// they only apply to the input. `serde` attributes are kept only when `keep_serde` is set or the
+/// same `cfg_attr` derives serde, otherwise they would be unknown on the generated struct.
+pub(crate) fn forwarded_test_attrs(
+    attrs: &[syn::Attribute],
+    keep_serde: bool,
+) -> Vec<proc_macro2::TokenStream> {
+    let is_own = |path: &Path| path.is_ident("unwrapped") || path.is_ident("wrapped");
+    let is_own_derive = |path: &Path| {
+        path.segments
+            .last()
+            .is_some_and(|seg| seg.ident == "Unwrapped" || seg.ident == "Wrapped")
+    };
+
+    attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("cfg_attr"))
+        .filter_map(|attr| {
+            let metas = attr
+                .parse_args_with(
+                    syn::punctuated::Punctuated::<Meta, syn::Token![,]>::parse_terminated,
+                )
+                .ok()?;
+            let mut metas = metas.into_iter();
+            match metas.next()? {
+                Meta::Path(path) if path.is_ident("test") => {},
+                _ => return None,
+            }
+
+            let mut kept = Vec::new();
+            for meta in metas {
+                if is_own(meta.path()) {
+                    continue;
+                }
+                if meta.path().is_ident("derive") {
+                    let derives = parse_derive_list(&meta).ok()?;
+                    let derives: Vec<proc_macro2::TokenStream> = derives
+                        .into_iter()
+                        .filter(|tokens| {
+                            syn::parse2::<Path>(tokens.clone())
+                                .map_or(true, |path| !is_own_derive(&path))
+                        })
+                        .collect();
+                    if !derives.is_empty() {
+                        kept.push(quote! { derive(#(#derives),*) });
+                    }
+                    continue;
+                }
+                kept.push(quote! { #meta });
+            }
+
+            let keep_serde = keep_serde || derives_serde(&kept);
+            kept.retain(|tokens| {
+                keep_serde
+                    || syn::parse2::<Meta>(tokens.clone())
+                        .map_or(true, |meta| !meta.path().is_ident("serde"))
+            });
+            (!kept.is_empty()).then(|| quote! { #[cfg_attr(test, #(#kept),*)] })
+        })
+        .collect()
+}
+
+/// The `///` doc comments of an input field, re-emitted on the generated field
+pub(crate) fn doc_attrs(attrs: &[syn::Attribute]) -> Vec<&syn::Attribute> {
+    attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("doc"))
+        .collect()
+}
+
+/// The `#[cfg(...)]` attributes of an input field, repeated on everything generated for it so the
+/// generated struct and its conversions agree on the fields under every feature set
+pub(crate) fn cfg_attrs(attrs: &[syn::Attribute]) -> Vec<&syn::Attribute> {
+    attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("cfg"))
+        .collect()
+}
+
+/// Generate code for each field, every piece gated by the field's `#[cfg(...)]` attributes
+///
+/// `generate` returns either the code or an `Option` of it, to leave a field out.
+pub(crate) fn gated_fields<'a, R>(
+    fields: &'a syn::Fields,
+    mut generate: impl FnMut(&'a syn::Field) -> R + 'a,
+) -> impl Iterator<Item = proc_macro2::TokenStream> + 'a
+where
+    R: Into<Option<proc_macro2::TokenStream>>,
+{
+    fields.iter().filter_map(move |f| {
+        let tokens = generate(f).into()?;
+        let cfgs = cfg_attrs(&f.attrs);
+        Some(quote! { #(#cfgs)* #tokens })
+    })
+}
+
+/// Number of generated fields, counted where the code is compiled when some sit behind
+/// `#[cfg(...)]`
+pub(crate) fn field_count<'a>(
+    fields: impl IntoIterator<Item = &'a syn::Field>,
+) -> proc_macro2::TokenStream {
+    let fields: Vec<&syn::Field> = fields.into_iter().collect();
+    if fields.iter().all(|f| cfg_attrs(&f.attrs).is_empty()) {
+        let count = fields.len();
+        return quote! { #count };
+    }
+
+    let increments = fields.iter().map(|f| {
+        let cfgs = cfg_attrs(&f.attrs);
+        quote! { #(#cfgs)* { count += 1; } }
+    });
+    quote! {
+        {
+            let mut count = 0usize;
+            #(#increments)*
+            count
+        }
+    }
+}
+
+/// The function named by the field's `#[serde(default = "path")]`, if any
+pub(crate) fn serde_default_fn(f: &syn::Field) -> Option<syn::Path> {
+    f.attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("serde"))
+        .filter_map(|attr| {
+            attr.parse_args_with(
+                syn::punctuated::Punctuated::<Meta, syn::Token![,]>::parse_terminated,
+            )
+            .ok()
+        })
+        .flatten()
+        .find_map(|meta| match meta {
+            Meta::NameValue(syn::MetaNameValue {
+                path,
+                value:
+                    Expr::Lit(syn::ExprLit {
+                        lit: syn::Lit::Str(lit),
+                        ..
+                    }),
+                ..
+            }) if path.is_ident("default") => lit.parse().ok(),
+            _ => None,
+        })
+}
+
+/// Whether the field's `#[serde(...)]` attributes contain the bare `key`
+pub(crate) fn has_serde_flag(f: &syn::Field, key: &str) -> bool {
+    f.attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("serde"))
+        .filter_map(|attr| {
+            attr.parse_args_with(
+                syn::punctuated::Punctuated::<Meta, syn::Token![,]>::parse_terminated,
+            )
+            .ok()
+        })
+        .any(|metas| {
+            metas
+                .iter()
+                .any(|meta| matches!(meta, Meta::Path(path) if path.is_ident(key)))
+        })
+}
+
+/// Whether the generated struct derives `Serialize` or `Deserialize`
+pub(crate) fn derives_serde(struct_derives: &[proc_macro2::TokenStream]) -> bool {
+    struct_derives.iter().any(|tokens| {
+        tokens.clone().into_iter().any(|token| {
+            matches!(token, proc_macro2::TokenTree::Ident(ident) if ident == "Serialize" || ident == "Deserialize")
+        })
+    })
+}
+
+/// Parse `derive(A, b::C)` from the derive attribute into a list of derive paths
+pub(crate) fn parse_derive_list(meta: &Meta) -> darling::Result<Vec<proc_macro2::TokenStream>> {
+    let list = meta.require_list()?;
+    let paths = list
+        .parse_args_with(syn::punctuated::Punctuated::<Path, syn::Token![,]>::parse_terminated)?;
+    Ok(paths.iter().map(|path| quote! { #path }).collect())
+}
+
+/// Parse `attr(a, b(c))` from the derive attribute into `#[a]`, `#[b(c)]`
+pub(crate) fn parse_attr_list(meta: &Meta) -> darling::Result<Vec<proc_macro2::TokenStream>> {
+    let list = meta.require_list()?;
+    let metas = parse_meta_list(list.tokens.clone())
+        .ok_or_else(|| darling::Error::custom("Expected a list of attributes").with_span(list))?;
+    Ok(metas.iter().map(|meta| quote! { #[#meta] }).collect())
+}
+
+/// Parse `only("a", "b")` from the derive attribute into a list of field names
+pub(crate) fn parse_name_list(meta: &Meta) -> darling::Result<Vec<syn::LitStr>> {
+    let list = meta.require_list()?;
+    let names = list.parse_args_with(
+        syn::punctuated::Punctuated::<syn::LitStr, syn::Token![,]>::parse_terminated,
+    )?;
+    Ok(names.into_iter().collect())
+}
+
+/// Parse a type given as a path, `ty = u16`, or as a string, `ty = "Vec<u8>"`
+pub(crate) fn parse_field_type(meta: &Meta) -> darling::Result<Option<syn::Type>> {
+    if let Meta::NameValue(nv) = meta
+        && let Expr::Path(p) = &nv.value
+        && p.qself.is_none()
+    {
+        return Ok(Some(syn::Type::Path(syn::TypePath {
+            qself: None,
+            path: p.path.clone(),
+        })));
+    }
+    <syn::Type as darling::FromMeta>::from_meta(meta).map(Some)
+}
+
+/// Parse a field `default`, a bare `default` stands for `Default::default()`
+pub(crate) fn parse_field_default(meta: &Meta) -> darling::Result<Option<syn::Expr>> {
+    match meta {
+        Meta::Path(_) => Ok(Some(syn::parse_quote!(::core::default::Default::default()))),
+        _ => <syn::Expr as darling::FromMeta>::from_meta(meta).map(Some),
+    }
+}
+
+/// Check that every name in a struct-level field list matches a field of the struct
+pub(crate) fn check_field_names(s: &syn::DataStruct, names: &[syn::LitStr]) -> syn::Result<()> {
+    for name in names {
+        let value = name.value();
+        if !s.fields.iter().any(|f| field_key(s, f) == value) {
+            return Err(syn::Error::new_spanned(
+                name,
+                format!("no field named `{}`", value),
+            ));
+        }
+    }
+    Ok(())
 }
 
 /// Build the derive output based on struct_derives
+///
+/// Each entry may hold several comma-separated derives. Derives whose paths end in the same
+/// ident are emitted once, keeping the first, so `Clone` and `::core::clone::Clone` collapse.
 pub fn build_derive_output(
     struct_derives: &[proc_macro2::TokenStream],
 ) -> proc_macro2::TokenStream {
-    if struct_derives.is_empty() {
-        quote! { #[derive()] }
-    } else {
-        quote! { #[derive(#(#struct_derives),*)] }
+    let parser = syn::punctuated::Punctuated::<Path, syn::Token![,]>::parse_terminated;
+    let mut seen = HashSet::new();
+    let mut derives = Vec::new();
+    for tokens in struct_derives {
+        let Ok(paths) = parser.parse2(tokens.clone()) else {
+            // Not a plain path list, emit it untouched
+            derives.push(tokens.clone());
+            continue;
+        };
+        for path in paths {
+            let key = path
+                .segments
+                .last()
+                .map_or_else(String::new, |seg| seg.ident.to_string());
+            if seen.insert(key) {
+                derives.push(quote! { #path });
+            }
+        }
+    }
+
+    quote! { #[derive(#(#derives),*)] }
+}
+
+/// Derives that `f32` and `f64` do not implement
+const FLOAT_INCOMPATIBLE_DERIVES: &[&str] = &["Eq", "Hash", "Ord"];
+
+fn mentions_float(tokens: proc_macro2::TokenStream) -> bool {
+    tokens.into_iter().any(|token| match token {
+        proc_macro2::TokenTree::Ident(ident) => ident == "f32" || ident == "f64",
+        proc_macro2::TokenTree::Group(group) => mentions_float(group.stream()),
+        _ => false,
+    })
+}
+
+/// Warn about float fields when the generated struct derives a trait floats do not implement.
+///
+/// This is best-effort: only types naming `f32` or `f64` are caught. Proc macros cannot emit
+/// warnings on stable, so each warning is the use of a `#[deprecated]` constant spanned at the
+/// field type.
+pub(crate) fn float_derive_warnings<'a>(
+    struct_derives: &[proc_macro2::TokenStream],
+    fields: impl IntoIterator<Item = (String, &'a syn::Type)>,
+) -> proc_macro2::TokenStream {
+    let parser = syn::punctuated::Punctuated::<Path, syn::Token![,]>::parse_terminated;
+    let mut incompatible = Vec::new();
+    for tokens in struct_derives {
+        let Ok(paths) = parser.parse2(tokens.clone()) else {
+            continue;
+        };
+        for path in paths {
+            if let Some(seg) = path.segments.last()
+                && let Some(derive) = FLOAT_INCOMPATIBLE_DERIVES
+                    .iter()
+                    .find(|derive| seg.ident == derive)
+                && !incompatible.contains(derive)
+            {
+                incompatible.push(*derive);
+            }
+        }
+    }
+    if incompatible.is_empty() {
+        return quote! {};
     }
+
+    let derives = incompatible
+        .iter()
+        .map(|derive| format!("`{}`", derive))
+        .collect::<Vec<_>>()
+        .join(", ");
+    let warnings = fields
+        .into_iter()
+        .filter(|(_, ty)| mentions_float(quote! { #ty }))
+        .map(|(name, ty)| {
+            let note = format!(
+                "field `{}` has a float type, which does not implement {} derived on the generated struct",
+                name, derives
+            );
+            quote_spanned! { ty.span()=>
+                const _: () = {
+                    #[deprecated(note = #note)]
+                    const FLOAT_FIELD: () = ();
+                    FLOAT_FIELD
+                };
+            }
+        });
+
+    quote! { #(#warnings)* }
 }
 
 #[derive(Default)]
@@ -264,6 +957,41 @@ fn parse_builder_config(attrs: &[syn::Attribute]) -> BonBuilderConfig {
     config
 }
 
+/// How bon treats a single field: `required` keeps an `Option<T>` field on a plain setter, while
+/// `default` turns any field into an optional member with a `maybe_` setter.
+#[derive(Default)]
+pub(crate) struct BonMemberConfig {
+    pub(crate) required: bool,
+    pub(crate) has_default: bool,
+}
+
+pub(crate) fn bon_member_config(attrs: &[syn::Attribute]) -> BonMemberConfig {
+    let mut config = BonMemberConfig::default();
+
+    for attr in attrs {
+        if !attr.path().is_ident("builder") {
+            continue;
+        }
+        let Meta::List(meta) = &attr.meta else {
+            continue;
+        };
+        let Some(nested) = parse_meta_list(meta.tokens.clone()) else {
+            continue;
+        };
+
+        for item in nested {
+            if item.path().is_ident("required") {
+                config.required = true;
+            }
+            if item.path().is_ident("default") {
+                config.has_default = true;
+            }
+        }
+    }
+
+    config
+}
+
 fn parse_builder_item_ident(item: &Meta, key: &str) -> Option<syn::Ident> {
     match item {
         Meta::NameValue(nv) if nv.path.is_ident(key) => parse_meta_value_ident(&nv.value),
@@ -305,7 +1033,13 @@ fn parse_meta_value_ident(expr: &Expr) -> Option<syn::Ident> {
 }
 
 pub(crate) fn bon_builder_info(input: &DeriveInput) -> Option<BonBuilderInfo> {
-    if !derives_builder(&input.attrs) && !has_builder_attr(&input.attrs) {
+    // The invoking `#[derive(...)]` is stripped before we run, so field-level `#[builder(...)]`
+    // is often the only sign that bon is in play
+    let field_builder_attr = match &input.data {
+        syn::Data::Struct(data) => data.fields.iter().any(|f| has_builder_attr(&f.attrs)),
+        _ => false,
+    };
+    if !derives_builder(&input.attrs) && !has_builder_attr(&input.attrs) && !field_builder_attr {
         return None;
     }
 
@@ -394,3 +1128,87 @@ pub(crate) fn generic_args(generics: &syn::Generics) -> Vec<proc_macro2::TokenSt
         })
         .collect()
 }
+
+/// Generics for a generated struct, dropping lifetimes that none of its fields use anymore.
+///
+/// Skipping a field can remove the last use of a lifetime, which would otherwise be an unused
+/// parameter on the generated struct. Bounds and where predicates naming a dropped lifetime are
+/// removed along with it.
+pub(crate) fn mirror_generics<'a>(
+    generics: &syn::Generics,
+    field_types: impl IntoIterator<Item = &'a syn::Type>,
+) -> syn::Generics {
+    let field_tokens: Vec<proc_macro2::TokenStream> =
+        field_types.into_iter().map(|ty| quote! { #ty }).collect();
+    let unused: Vec<syn::Lifetime> = generics
+        .lifetimes()
+        .map(|param| &param.lifetime)
+        .filter(|lifetime| {
+            !field_tokens
+                .iter()
+                .any(|tokens| mentions_lifetime(tokens.clone(), lifetime))
+        })
+        .cloned()
+        .collect();
+    if unused.is_empty() {
+        return generics.clone();
+    }
+
+    let mentions_unused = |tokens: proc_macro2::TokenStream| {
+        unused
+            .iter()
+            .any(|lifetime| mentions_lifetime(tokens.clone(), lifetime))
+    };
+
+    let mut mirror = generics.clone();
+    mirror.params = mirror
+        .params
+        .into_iter()
+        .filter(|param| !matches!(param, GenericParam::Lifetime(param) if unused.contains(&param.lifetime)))
+        .map(|mut param| {
+            match &mut param {
+                GenericParam::Lifetime(param) => {
+                    param.bounds = std::mem::take(&mut param.bounds)
+                        .into_iter()
+                        .filter(|bound| !unused.contains(bound))
+                        .collect();
+                },
+                GenericParam::Type(param) => {
+                    param.bounds = std::mem::take(&mut param.bounds)
+                        .into_iter()
+                        .filter(|bound| !mentions_unused(quote! { #bound }))
+                        .collect();
+                },
+                GenericParam::Const(_) => {},
+            }
+            param
+        })
+        .collect();
+    if let Some(where_clause) = &mut mirror.where_clause {
+        where_clause.predicates = std::mem::take(&mut where_clause.predicates)
+            .into_iter()
+            .filter(|predicate| !mentions_unused(quote! { #predicate }))
+            .collect();
+    }
+    mirror
+}
+
+fn mentions_lifetime(tokens: proc_macro2::TokenStream, lifetime: &syn::Lifetime) -> bool {
+    let mut tokens = tokens.into_iter().peekable();
+    while let Some(token) = tokens.next() {
+        match token {
+            proc_macro2::TokenTree::Group(group) if mentions_lifetime(group.stream(), lifetime) => {
+                return true;
+            },
+            proc_macro2::TokenTree::Punct(punct) if punct.as_char() == '\'' => {
+                if let Some(proc_macro2::TokenTree::Ident(ident)) = tokens.peek()
+                    && *ident == lifetime.ident
+                {
+                    return true;
+                }
+            },
+            _ => {},
+        }
+    }
+    false
+}
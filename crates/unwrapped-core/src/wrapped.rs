@@ -6,45 +6,177 @@ use quote::{format_ident, quote};
 use syn::DeriveInput;
 
 use crate::utils::{
-    CommonOpts, ProcUsageOpts, bon_builder_info, build_derive_output, collect_field_attrs,
-    generic_args, get_struct_data, is_option_type, raw_ident_name, snake_to_pascal_ident,
-    unique_state_ident,
+    CommonOpts, FieldContext, ProcUsageOpts, bon_builder_info, bon_member_config,
+    build_derive_output, cfg_attrs, check_field_names, collect_field_attrs, derives_serde,
+    doc_attrs, field_count, field_position, float_derive_warnings, forwarded_container_attrs,
+    forwarded_test_attrs, gated_fields, generic_args, get_struct_data, is_option_type, lib_path,
+    mirror_generics, missing_error, parse_attr_list, parse_derive_list, parse_name_list,
+    raw_ident_name, snake_to_pascal_ident, unique_state_ident, with_where_predicates,
 };
 
 #[derive(Clone, Debug, Default, FromField)]
 #[darling(default, attributes(wrapped))]
-struct WrappedFieldOpts {
-    skip: bool,
+pub(crate) struct WrappedFieldOpts {
+    pub(crate) skip: bool,
+
+    /// Attributes to add to this field on the generated struct
+    #[darling(rename = "attr", with = parse_attr_list)]
+    extra_attrs: Vec<proc_macro2::TokenStream>,
+
+    /// Value used by `from_partial_with_defaults` when this field is missing
+    default: Option<syn::Expr>,
+
+    /// Name of this field on the generated struct, every option still refers to the original name
+    pub(crate) rename: Option<syn::Ident>,
+
+    /// Let `merge_into` overwrite this already-`Option` field with `None` instead of keeping it
+    merge_replace: bool,
+}
+
+impl WrappedFieldOpts {
+    /// Name of the field on the generated struct
+    fn generated_ident(&self, f: &syn::Field) -> syn::Ident {
+        self.rename
+            .clone()
+            .unwrap_or_else(|| f.ident.clone().expect("Expected named field"))
+    }
 }
 
 #[derive(Builder, Clone, Debug, FromDeriveInput)]
-#[darling(attributes(wrapped), supports(struct_any))]
+#[darling(attributes(wrapped), supports(struct_any, enum_any))]
 pub struct WrappedOpts {
     name: Option<syn::Ident>,
     prefix: Option<syn::Ident>,
     suffix: Option<syn::Ident>,
 
+    /// Name of the generated inherent conversion method, `try_from` by default
+    method_name: Option<syn::Ident>,
+
+    /// Path to the `unwrapped` crate, overriding the proc usage `lib_holder_name`
+    #[darling(rename = "crate")]
+    krate: Option<syn::Path>,
+
     /// Custom derives to add to the generated struct (in addition to Clone, Debug, Default)
     #[builder(default)]
-    #[darling(skip)]
+    #[darling(default, rename = "derive", with = parse_derive_list)]
     struct_derives: Vec<proc_macro2::TokenStream>,
 
     /// Custom attributes to add to the generated struct
     #[builder(default)]
-    #[darling(skip)]
+    #[darling(default, rename = "attr", with = parse_attr_list)]
     struct_attrs: Vec<proc_macro2::TokenStream>,
 
     /// Per-field attributes to add to specific fields
     #[builder(default)]
     #[darling(skip)]
     field_attrs: HashMap<String, Vec<proc_macro2::TokenStream>>,
+
+    /// Extra `where` predicates for the generated struct and every generated impl
+    #[builder(default)]
+    #[darling(skip)]
+    where_predicates: Vec<syn::WherePredicate>,
+
+    /// Generate `diff_wrapped(old, new)`, building a patch of the fields that changed
+    #[builder(default)]
+    #[darling(default)]
+    diff: bool,
+
+    /// Generate `apply_all(base, patches)`, folding a sequence of patches onto a base value
+    #[builder(default)]
+    #[darling(default)]
+    apply_all: bool,
+
+    /// Generate `changed_fields`, listing the wrapped fields a patch sets
+    #[builder(default)]
+    #[darling(default)]
+    changed_fields: bool,
+
+    /// Generate `iter_set`, iterating over the wrapped fields a patch sets with their values
+    #[builder(default)]
+    #[darling(default)]
+    iter_set: bool,
+
+    /// Generate `clear`, resetting every `Option` field to `None`
+    #[builder(default)]
+    #[darling(default)]
+    clearable: bool,
+
+    /// Generate `impl PartialEq<Wrapped> for Original`, where `None` fields of the patch match anything
+    #[builder(default)]
+    #[darling(default)]
+    cmp: bool,
+
+    /// Generate a `{Wrapped}Field` enum with one variant per field, and `set` to assign one
+    #[builder(default)]
+    #[darling(default)]
+    field_enum: bool,
+
+    /// Generate `from_partial_with_defaults`, filling missing fields from their `default`
+    #[builder(default)]
+    #[darling(default)]
+    defaults_fill: bool,
+
+    /// Wrapped fields that are already `Option<T>` become `Option<Option<T>>`, so a patch can
+    /// tell an absent field from an explicit `None`
+    #[builder(default)]
+    #[darling(default)]
+    pub(crate) tristate: bool,
+
+    /// Only these fields become `Option<T>`, every other field keeps its type
+    #[builder(default)]
+    #[darling(default, with = parse_name_list)]
+    nullable: Vec<syn::LitStr>,
+
+    /// Give the generated struct the visibility of the input struct instead of `pub`
+    #[builder(default)]
+    #[darling(default)]
+    inherit_vis: bool,
+
+    /// Make every generated field `pub` instead of mirroring the visibility of the input field
+    #[builder(default)]
+    #[darling(default)]
+    all_pub: bool,
+
+    /// Don't copy the input fields' doc comments onto the generated fields
+    #[builder(default)]
+    #[darling(default)]
+    no_docs: bool,
+
+    /// Attribute namespaces copied verbatim from the input struct and its fields, e.g. `diesel`
+    #[builder(default)]
+    #[darling(default, with = parse_name_list)]
+    forward_attrs: Vec<syn::LitStr>,
+
+    /// Attribute namespaces never copied from the input, overriding `forward_attrs` and defaults
+    #[builder(default)]
+    #[darling(default, with = parse_name_list)]
+    skip_attrs: Vec<syn::LitStr>,
 }
 
 impl WrappedOpts {
+    /// Visibility of a field on the generated struct
+    fn field_vis(&self, f: &syn::Field) -> syn::Visibility {
+        if self.all_pub {
+            syn::parse_quote!(pub)
+        } else {
+            f.vis.clone()
+        }
+    }
+
     pub fn wrapped_ident(&self, original_ident: &syn::Ident) -> syn::Ident {
         self.to_common().generate_ident(original_ident, "W")
     }
 
+    /// Whether the `nullable` list, if any, lets this field be wrapped
+    pub(crate) fn is_nullable(&self, f: &syn::Field) -> bool {
+        self.nullable.is_empty()
+            || self.nullable.iter().any(|nullable| {
+                f.ident
+                    .as_ref()
+                    .is_some_and(|ident| *ident == nullable.value())
+            })
+    }
+
     /// Add a derive to the generated struct
     pub fn with_derive(mut self, tokens: impl Into<proc_macro2::TokenStream>) -> Self {
         self.struct_derives.push(tokens.into());
@@ -63,6 +195,14 @@ impl WrappedOpts {
         self
     }
 
+    /// Add a `where` predicate to the generated struct and every generated impl, e.g.
+    /// `quote!(T: Serialize)` for a derive whose bounds differ from the input struct's
+    pub fn with_where(mut self, tokens: impl Into<proc_macro2::TokenStream>) -> Self {
+        self.where_predicates
+            .push(syn::parse2(tokens.into()).expect("Expected a where predicate"));
+        self
+    }
+
     /// Add an attribute to a specific field by name
     pub fn with_field_attr(
         mut self,
@@ -85,6 +225,8 @@ impl WrappedOpts {
             struct_derives: self.struct_derives.clone(),
             struct_attrs: self.struct_attrs.clone(),
             field_attrs: self.field_attrs.clone(),
+            forward_attrs: self.forward_attrs.iter().map(syn::LitStr::value).collect(),
+            skip_attrs: self.skip_attrs.iter().map(syn::LitStr::value).collect(),
         }
     }
 }
@@ -113,29 +255,34 @@ impl FieldProcOpts {
 #[derive(Clone, Debug, Default)]
 pub struct WrappedProcUsageOpts {
     pub fields_to_wrap: HashMap<String, bool>,
-    lib_holder_name: Option<syn::Ident>,
+    lib_holder_name: Option<syn::Path>,
     /// Field transformations: name -> (should_wrap, attributes)
     pub field_opts: HashMap<String, FieldProcOpts>,
     /// Dynamic field attribute generator
     pub field_attr_fn: Option<fn(&syn::Field) -> Option<proc_macro2::TokenStream>>,
+    /// Dynamic field attribute generator that also sees the field's position and struct
+    pub field_attr_fn_ctx: Option<fn(&FieldContext<'_>) -> Option<proc_macro2::TokenStream>>,
 }
 
 impl WrappedProcUsageOpts {
-    pub fn new(fields_to_wrap: HashMap<String, bool>, lib_holder_name: Option<syn::Ident>) -> Self {
+    pub fn new(fields_to_wrap: HashMap<String, bool>, lib_holder_name: Option<syn::Path>) -> Self {
         Self {
             fields_to_wrap,
             lib_holder_name,
             field_opts: HashMap::new(),
             field_attr_fn: None,
+            field_attr_fn_ctx: None,
         }
     }
 
     pub fn lib_path(&self) -> syn::Path {
-        if let Some(name) = &self.lib_holder_name {
-            syn::parse_str(&format!("{}::unwrapped", name)).unwrap()
-        } else {
-            syn::parse_str("unwrapped").unwrap()
-        }
+        lib_path(self.lib_holder_name.as_ref())
+    }
+
+    /// Set the crate that re-exports `unwrapped`, or the full path to it, see `lib_path`
+    pub fn with_lib_holder(mut self, name: impl Into<syn::Path>) -> Self {
+        self.lib_holder_name = Some(name.into());
+        self
     }
 
     /// Set options for a specific field
@@ -145,6 +292,18 @@ impl WrappedProcUsageOpts {
         self
     }
 
+    /// Set options for many fields at once, later entries override earlier ones
+    pub fn with_field_opts_bulk<K: AsRef<str>>(
+        mut self,
+        field_opts: impl IntoIterator<Item = (K, FieldProcOpts)>,
+    ) -> Self {
+        for (field_name, opts) in field_opts {
+            self.field_opts
+                .insert(field_name.as_ref().to_string(), opts);
+        }
+        self
+    }
+
     /// Set a dynamic field attribute generator
     pub fn with_field_attr_fn(
         mut self,
@@ -154,6 +313,15 @@ impl WrappedProcUsageOpts {
         self
     }
 
+    /// Set a dynamic field attribute generator that also sees the field's position and struct
+    pub fn with_field_attr_fn_ctx(
+        mut self,
+        f: fn(&FieldContext<'_>) -> Option<proc_macro2::TokenStream>,
+    ) -> Self {
+        self.field_attr_fn_ctx = Some(f);
+        self
+    }
+
     fn to_common(&self) -> ProcUsageOpts {
         let mut field_opts = HashMap::new();
         for (name, opts) in &self.field_opts {
@@ -170,6 +338,250 @@ impl WrappedProcUsageOpts {
             lib_holder_name: self.lib_holder_name.clone(),
             field_opts,
             field_attr_fn: self.field_attr_fn,
+            field_attr_fn_ctx: self.field_attr_fn_ctx,
+        }
+    }
+}
+
+/// Generate the mirror enum, wrapping every non-`Option` field of every variant in `Option`.
+///
+/// Named, tuple and unit variants are supported. A `None` field fails `try_from` with a
+/// `field_name` of `Variant.field`, or `Variant.0` for tuple variants.
+fn wrapped_enum(
+    input: &DeriveInput,
+    data: &syn::DataEnum,
+    opts: &WrappedOpts,
+    proc_usage_opts: &WrappedProcUsageOpts,
+    lib_path: &syn::Path,
+) -> proc_macro2::TokenStream {
+    let original_ident = &input.ident;
+
+    // These helpers are built around the fields of a single struct
+    for (enabled, option) in [
+        (opts.diff, "diff"),
+        (opts.apply_all, "apply_all"),
+        (opts.changed_fields, "changed_fields"),
+        (opts.iter_set, "iter_set"),
+        (opts.clearable, "clearable"),
+        (opts.cmp, "cmp"),
+        (opts.field_enum, "field_enum"),
+        (opts.defaults_fill, "defaults_fill"),
+        (!opts.nullable.is_empty(), "nullable"),
+        (opts.all_pub, "all_pub"),
+    ] {
+        if enabled {
+            return syn::Error::new_spanned(
+                original_ident,
+                format!("`{}` is not supported on enums", option),
+            )
+            .to_compile_error();
+        }
+    }
+
+    let wrapped_ident = &opts.wrapped_ident(original_ident);
+    let try_from_ident = opts
+        .method_name
+        .clone()
+        .unwrap_or_else(|| format_ident!("try_from"));
+
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let mirror_generics = mirror_generics(
+        &input.generics,
+        data.variants
+            .iter()
+            .flat_map(|v| v.fields.iter().map(|f| &f.ty)),
+    );
+    let (mirror_impl_generics, mirror_ty_generics, mirror_where_clause) =
+        mirror_generics.split_for_impl();
+
+    let vis: syn::Visibility = if opts.inherit_vis {
+        input.vis.clone()
+    } else {
+        syn::parse_quote!(pub)
+    };
+
+    let common_opts = opts.to_common();
+    let common_proc_opts = proc_usage_opts.to_common();
+
+    let mut variants = Vec::new();
+    let mut to_wrapped_arms = Vec::new();
+    let mut try_from_arms = Vec::new();
+    let mut field_count = 0usize;
+
+    for v in data.variants.iter() {
+        let variant_ident = &v.ident;
+        // Field attributes are collected per variant, as if it were a struct of its own
+        let variant_struct = syn::DataStruct {
+            struct_token: Default::default(),
+            fields: v.fields.clone(),
+            semi_token: None,
+        };
+
+        let mut fields = Vec::new();
+        let mut bindings = Vec::new();
+        let mut to_wrapped_values = Vec::new();
+        let mut try_from_values = Vec::new();
+        for (i, f) in variant_struct.fields.iter().enumerate() {
+            let field_opts = WrappedFieldOpts::from_field(f).expect("Wrong field options");
+            // A skipped field leaves no value to rebuild the variant from
+            if field_opts.skip {
+                return syn::Error::new_spanned(
+                    f,
+                    "`skip` is not supported on enum variant fields",
+                )
+                .to_compile_error();
+            }
+            for (set, option) in [
+                (field_opts.rename.is_some(), "rename"),
+                (field_opts.default.is_some(), "default"),
+                (field_opts.merge_replace, "merge_replace"),
+            ] {
+                if set {
+                    return syn::Error::new_spanned(
+                        f,
+                        format!("`{}` is not supported on enum variant fields", option),
+                    )
+                    .to_compile_error();
+                }
+            }
+            if let Some(cfg) = cfg_attrs(&f.attrs).first() {
+                return syn::Error::new_spanned(
+                    cfg,
+                    "`#[cfg(...)]` is not supported on enum variant fields",
+                )
+                .to_compile_error();
+            }
+
+            let binding = f
+                .ident
+                .clone()
+                .unwrap_or_else(|| format_ident!("__field{}", i));
+            let label = match &f.ident {
+                Some(name) => format!("{}.{}", variant_ident, raw_ident_name(name)),
+                None => format!("{}.{}", variant_ident, i),
+            };
+            let vis = &f.vis;
+            let name = f.ident.as_ref().map(|name| quote! { #name: });
+            let ty = &f.ty;
+
+            let ctx = FieldContext {
+                field: f,
+                index: i,
+                struct_ident: original_ident,
+                transformed: opts.tristate || is_option_type(ty).is_none(),
+            };
+            let field_attrs = collect_field_attrs(
+                &variant_struct,
+                &ctx,
+                &field_opts.extra_attrs,
+                &common_opts,
+                &common_proc_opts,
+            );
+            let docs = if opts.no_docs || common_opts.skips_attr("doc") {
+                Vec::new()
+            } else {
+                doc_attrs(&f.attrs)
+            };
+            let attrs = quote! { #(#docs)* #(#field_attrs)* };
+
+            if opts.tristate && is_option_type(ty).is_some() {
+                fields.push(quote! { #attrs #vis #name ::core::option::Option<#ty> });
+                to_wrapped_values.push(quote! { Some(#binding) });
+                try_from_values.push(quote! { #binding.flatten() });
+            } else if is_option_type(ty).is_some() {
+                fields.push(quote! { #attrs #vis #name #ty });
+                to_wrapped_values.push(quote! { #binding });
+                try_from_values.push(quote! { #binding });
+            } else {
+                fields.push(quote! { #attrs #vis #name ::core::option::Option<#ty> });
+                to_wrapped_values.push(quote! { Some(#binding) });
+                let missing = missing_error(lib_path, &label, &quote! { "" });
+                try_from_values.push(quote! { #binding.ok_or(#missing)? });
+            }
+            bindings.push(binding);
+            field_count += 1;
+        }
+
+        let names = v.fields.iter().map(|f| &f.ident);
+        match &v.fields {
+            syn::Fields::Named(_) => {
+                let names: Vec<_> = names.collect();
+                variants.push(quote! { #variant_ident { #(#fields),* } });
+                to_wrapped_arms.push(quote! {
+                    #original_ident::#variant_ident { #(#bindings),* } => Self::#variant_ident { #(#names: #to_wrapped_values),* }
+                });
+                try_from_arms.push(quote! {
+                    #wrapped_ident::#variant_ident { #(#bindings),* } => #original_ident::#variant_ident { #(#names: #try_from_values),* }
+                });
+            },
+            syn::Fields::Unnamed(_) => {
+                variants.push(quote! { #variant_ident(#(#fields),*) });
+                to_wrapped_arms.push(quote! {
+                    #original_ident::#variant_ident(#(#bindings),*) => Self::#variant_ident(#(#to_wrapped_values),*)
+                });
+                try_from_arms.push(quote! {
+                    #wrapped_ident::#variant_ident(#(#bindings),*) => #original_ident::#variant_ident(#(#try_from_values),*)
+                });
+            },
+            syn::Fields::Unit => {
+                variants.push(quote! { #variant_ident });
+                to_wrapped_arms
+                    .push(quote! { #original_ident::#variant_ident => Self::#variant_ident });
+                try_from_arms.push(
+                    quote! { #wrapped_ident::#variant_ident => #original_ident::#variant_ident },
+                );
+            },
+        }
+    }
+
+    let source_name = original_ident.to_string();
+    let mut struct_attrs = opts.struct_attrs.clone();
+    if !common_opts.skips_attr("cfg_attr") {
+        struct_attrs.extend(forwarded_test_attrs(
+            &input.attrs,
+            derives_serde(&opts.struct_derives),
+        ));
+    }
+    let derive_output = build_derive_output(&opts.struct_derives);
+    let forwarded_attrs = forwarded_container_attrs(&input.attrs, &common_opts);
+
+    quote! {
+        #(#struct_attrs)*
+        #derive_output
+        #(#forwarded_attrs)*
+        #vis enum #wrapped_ident #mirror_impl_generics #mirror_where_clause {
+            #(#variants),*
+        }
+
+        impl #impl_generics ::core::convert::From<#original_ident #ty_generics> for #wrapped_ident #mirror_ty_generics #where_clause {
+            fn from(from: #original_ident #ty_generics) -> Self {
+                match from {
+                    #(#to_wrapped_arms),*
+                }
+            }
+        }
+
+        impl #impl_generics #lib_path::Wrapped for #original_ident #ty_generics #where_clause {
+            type Wrapped = #wrapped_ident #mirror_ty_generics;
+
+            const FIELD_COUNT: usize = #field_count;
+            const SOURCE: &'static str = #source_name;
+        }
+
+        impl #impl_generics #wrapped_ident #mirror_ty_generics #where_clause {
+            pub fn #try_from_ident(from: #wrapped_ident #mirror_ty_generics) -> ::core::result::Result<#original_ident #ty_generics, #lib_path::UnwrappedError> {
+                Ok(match from {
+                    #(#try_from_arms),*
+                })
+            }
+        }
+
+        impl #impl_generics ::core::convert::TryFrom<#wrapped_ident #mirror_ty_generics> for #original_ident #ty_generics #where_clause {
+            type Error = #lib_path::UnwrappedError;
+
+            fn try_from(from: #wrapped_ident #mirror_ty_generics) -> ::core::result::Result<Self, Self::Error> {
+                #wrapped_ident::#try_from_ident(from)
+            }
         }
     }
 }
@@ -177,20 +589,113 @@ impl WrappedProcUsageOpts {
 pub fn wrapped(
     input: &DeriveInput,
     options: Option<WrappedOpts>,
-    proc_usage_opts: WrappedProcUsageOpts,
+    mut proc_usage_opts: WrappedProcUsageOpts,
 ) -> proc_macro2::TokenStream {
     let opts =
         options.unwrap_or_else(|| WrappedOpts::from_derive_input(input).expect("Wrong options"));
-    let lib_path = proc_usage_opts.lib_path();
-    let common_opts = opts.to_common();
-    let common_proc_opts = proc_usage_opts.to_common();
+    let input = &with_where_predicates(input, &opts.where_predicates);
+    let lib_path = opts
+        .krate
+        .clone()
+        .unwrap_or_else(|| proc_usage_opts.lib_path());
+    if let syn::Data::Enum(data) = &input.data {
+        return wrapped_enum(input, data, &opts, &proc_usage_opts, &lib_path);
+    }
 
     let original_ident = &input.ident;
     let wrapped_ident = &opts.wrapped_ident(original_ident);
+    let try_from_ident = opts
+        .method_name
+        .clone()
+        .unwrap_or_else(|| format_ident!("try_from"));
 
     let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
     let s = get_struct_data(input);
 
+    // A nullable list decides wrapping for every field, overriding `fields_to_wrap`
+    if !opts.nullable.is_empty() {
+        if let Err(err) = check_field_names(s, &opts.nullable) {
+            return err.to_compile_error();
+        }
+        for f in s.fields.iter() {
+            let name = f.ident.as_ref().unwrap().to_string();
+            let wrap = opts
+                .nullable
+                .iter()
+                .any(|nullable| nullable.value() == name);
+            proc_usage_opts.fields_to_wrap.insert(name, wrap);
+        }
+    }
+
+    let common_opts = opts.to_common();
+    let common_proc_opts = proc_usage_opts.to_common();
+
+    let mirror_generics = mirror_generics(
+        &input.generics,
+        s.fields.iter().filter_map(|f| {
+            let field_opts = WrappedFieldOpts::from_field(f).expect("Wrong field options");
+            (!field_opts.skip).then_some(&f.ty)
+        }),
+    );
+    let (mirror_impl_generics, mirror_ty_generics, mirror_where_clause) =
+        mirror_generics.split_for_impl();
+
+    let vis: syn::Visibility = if opts.inherit_vis {
+        input.vis.clone()
+    } else {
+        syn::parse_quote!(pub)
+    };
+
+    // Generate Completable - `Complete` names the original, so the impl would leak a private
+    // original through a `pub` wrapped struct unless both share a visibility
+    let completable_impl = if opts.inherit_vis || matches!(input.vis, syn::Visibility::Public(_)) {
+        quote! {
+            impl #impl_generics #lib_path::Completable for #wrapped_ident #mirror_ty_generics #where_clause {
+                type Complete = #original_ident #ty_generics;
+
+                fn complete(self) -> ::core::result::Result<Self::Complete, #lib_path::UnwrappedError> {
+                    Self::#try_from_ident(self)
+                }
+            }
+        }
+    } else {
+        quote! {}
+    };
+
+    // With `tristate`, a wrapped `Option<T>` field becomes `Option<Option<T>>` and is handled like
+    // any other wrapped field: `None` leaves it unset, `Some(None)` sets it to `None` and
+    // `Some(Some(value))` to `Some(value)`. Converting back treats an unset field as `None`.
+    let is_tristate = |f: &syn::Field| {
+        opts.tristate
+            && is_option_type(&f.ty).is_some()
+            && *proc_usage_opts
+                .fields_to_wrap
+                .get(&f.ident.as_ref().unwrap().to_string())
+                .unwrap_or(&true)
+    };
+
+    // Only a field that is `Option` on both sides can carry an explicit `None` to merge
+    if let Some(f) = s.fields.iter().find(|f| {
+        let field_opts = WrappedFieldOpts::from_field(f).expect("Wrong field options");
+        field_opts.merge_replace && is_option_type(&f.ty).is_none()
+    }) {
+        return syn::Error::new_spanned(
+            f,
+            "`merge_replace` only applies to fields that are already `Option`",
+        )
+        .to_compile_error();
+    }
+    if let Some(f) = s.fields.iter().find(|f| {
+        let field_opts = WrappedFieldOpts::from_field(f).expect("Wrong field options");
+        field_opts.merge_replace && is_tristate(f)
+    }) {
+        return syn::Error::new_spanned(
+            f,
+            "`merge_replace` has no effect with `tristate`, where `Some(None)` already clears the field",
+        )
+        .to_compile_error();
+    }
+
     // Check if any field has skip attribute
     let has_skipped_fields = s.fields.iter().any(|f| {
         let field_opts = WrappedFieldOpts::from_field(f).expect("Wrong field options");
@@ -198,7 +703,7 @@ pub fn wrapped(
     });
 
     // Generate wrapped struct fields - all non-Option<T> fields become Option<T>
-    let fields = s.fields.iter().filter_map(|f| {
+    let fields = gated_fields(&s.fields, |f| {
         let field_opts = WrappedFieldOpts::from_field(f).expect("Wrong field options");
 
         // Skip this field entirely if skip attribute is present
@@ -209,24 +714,47 @@ pub fn wrapped(
         let ty = &f.ty;
         let name_str = name.as_ref().unwrap().to_string();
 
-        let is_already_option = is_option_type(ty).is_some();
+        let is_already_option = is_option_type(ty).is_some() && !is_tristate(f);
         let should_process = *proc_usage_opts
             .fields_to_wrap
             .get(&name_str)
             .unwrap_or(&true);
 
         // Collect field attributes
-        let field_attrs = collect_field_attrs(f, &common_opts, &common_proc_opts);
+        let ctx = FieldContext {
+            field: f,
+            index: field_position(s, f),
+            struct_ident: original_ident,
+            transformed: should_process && !is_already_option,
+        };
+        let field_attrs = collect_field_attrs(
+            s,
+            &ctx,
+            &field_opts.extra_attrs,
+            &common_opts,
+            &common_proc_opts,
+        );
+
+        // Doc comments go first, ahead of every collected attribute
+        let docs = if opts.no_docs || common_opts.skips_attr("doc") {
+            Vec::new()
+        } else {
+            doc_attrs(&f.attrs)
+        };
 
+        let w_name = field_opts.generated_ident(f);
+        let field_vis = opts.field_vis(f);
         if is_already_option || !should_process {
-            Some(quote! { #(#field_attrs)* pub #name: #ty })
+            Some(quote! { #(#docs)* #(#field_attrs)* #field_vis #w_name: #ty })
         } else {
-            Some(quote! { #(#field_attrs)* pub #name: Option<#ty> })
+            Some(
+                quote! { #(#docs)* #(#field_attrs)* #field_vis #w_name: ::core::option::Option<#ty> },
+            )
         }
     });
 
     // Generate From<Wrapped> for Original - unwrap values (no defaults)
-    let _from_fields = s.fields.iter().filter_map(|f| {
+    let _from_fields = gated_fields(&s.fields, |f| {
         let field_opts = WrappedFieldOpts::from_field(f).expect("Wrong field options");
 
         // Skip this field if skip attribute is present
@@ -237,22 +765,29 @@ pub fn wrapped(
         let ty = &f.ty;
         let name_str = name.as_ref().unwrap().to_string();
 
-        let is_already_option = is_option_type(ty).is_some();
+        let is_already_option = is_option_type(ty).is_some() && !is_tristate(f);
         let should_process = *proc_usage_opts
             .fields_to_wrap
             .get(&name_str)
             .unwrap_or(&true);
 
+        let w_name = field_opts.generated_ident(f);
         if is_already_option || !should_process {
-            Some(quote! { #name: from.#name })
+            Some(quote! { #name: from.#w_name })
+        } else if is_tristate(f) {
+            Some(quote! { #name: from.#w_name.flatten() })
         } else {
-            let field_name_str = name.as_ref().unwrap().to_string();
-            Some(quote! { #name: from.#name.ok_or(::#lib_path::UnwrappedError{ field_name: #field_name_str })? })
+            let missing = missing_error(
+                &lib_path,
+                &name.as_ref().unwrap().to_string(),
+                &quote! { "" },
+            );
+            Some(quote! { #name: from.#w_name.ok_or(#missing)? })
         }
     });
 
     // Generate From<Original> for Wrapped - wrap values in Some()
-    let to_wrapped_fields = s.fields.iter().filter_map(|f| {
+    let to_wrapped_fields = gated_fields(&s.fields, |f| {
         let field_opts = WrappedFieldOpts::from_field(f).expect("Wrong field options");
 
         // Skip this field if skip attribute is present
@@ -263,21 +798,22 @@ pub fn wrapped(
         let ty = &f.ty;
         let name_str = name.as_ref().unwrap().to_string();
 
-        let is_already_option = is_option_type(ty).is_some();
+        let is_already_option = is_option_type(ty).is_some() && !is_tristate(f);
         let should_process = *proc_usage_opts
             .fields_to_wrap
             .get(&name_str)
             .unwrap_or(&true);
 
+        let w_name = field_opts.generated_ident(f);
         if is_already_option || !should_process {
-            Some(quote! { #name: from.#name })
+            Some(quote! { #w_name: from.#name })
         } else {
-            Some(quote! { #name: Some(from.#name) })
+            Some(quote! { #w_name: Some(from.#name) })
         }
     });
 
     // Generate try_from method for Wrapped -> Original (returns error if any required field is None)
-    let try_from_fields = s.fields.iter().filter_map(|f| {
+    let try_from_fields = gated_fields(&s.fields, |f| {
         let field_opts = WrappedFieldOpts::from_field(f).expect("Wrong field options");
 
         // Skip this field if skip attribute is present
@@ -288,25 +824,506 @@ pub fn wrapped(
         let ty = &f.ty;
         let name_str = name.as_ref().unwrap().to_string();
 
-        let is_already_option = is_option_type(ty).is_some();
-        let should_process = *proc_usage_opts.fields_to_wrap.get(&name_str).unwrap_or(&true);
+        let is_already_option = is_option_type(ty).is_some() && !is_tristate(f);
+        let should_process = *proc_usage_opts
+            .fields_to_wrap
+            .get(&name_str)
+            .unwrap_or(&true);
 
+        let w_name = field_opts.generated_ident(f);
         if is_already_option || !should_process {
-            Some(quote! { #name: from.#name })
+            Some(quote! { #name: from.#w_name })
+        } else if is_tristate(f) {
+            Some(quote! { #name: from.#w_name.flatten() })
         } else {
-            let field_name_str = name.as_ref().unwrap().to_string();
-            Some(quote! { #name: from.#name.ok_or(::#lib_path::UnwrappedError{ field_name: #field_name_str })? })
+            let missing = missing_error(
+                &lib_path,
+                &name.as_ref().unwrap().to_string(),
+                &quote! { "" },
+            );
+            Some(quote! { #name: from.#w_name.ok_or(#missing)? })
         }
     });
 
+    // Generate diff_wrapped - Some(new) for changed fields, None for unchanged ones
+    let diff_helper = if opts.diff {
+        let diff_fields = gated_fields(&s.fields, |f| {
+            let field_opts = WrappedFieldOpts::from_field(f).expect("Wrong field options");
+
+            // Skip this field if skip attribute is present
+            if field_opts.skip {
+                return None;
+            }
+            let name = &f.ident;
+            let ty = &f.ty;
+            let name_str = name.as_ref().unwrap().to_string();
+
+            let is_already_option = is_option_type(ty).is_some() && !is_tristate(f);
+            let should_process = *proc_usage_opts
+                .fields_to_wrap
+                .get(&name_str)
+                .unwrap_or(&true);
+
+            let w_name = field_opts.generated_ident(f);
+            if is_already_option {
+                Some(quote! { #w_name: if old.#name != new.#name { new.#name } else { None } })
+            } else if !should_process {
+                // Not wrapped, so there is no way to express "unchanged"
+                Some(quote! { #w_name: new.#name })
+            } else {
+                Some(
+                    quote! { #w_name: if old.#name != new.#name { Some(new.#name) } else { None } },
+                )
+            }
+        });
+
+        quote! {
+            impl #impl_generics #wrapped_ident #mirror_ty_generics #where_clause {
+                /// Build a patch holding only the fields that differ between `old` and `new`.
+                ///
+                /// Changed fields are `Some(new_value)`, unchanged fields are `None`.
+                pub fn diff_wrapped(old: #original_ident #ty_generics, new: #original_ident #ty_generics) -> Self {
+                    Self {
+                        #(#diff_fields),*
+                    }
+                }
+            }
+        }
+    } else {
+        quote! {}
+    };
+
+    // Generate PartialEq<Wrapped> - a patch matches when each field it sets equals the original
+    let cmp_helper = if opts.cmp {
+        let checks = gated_fields(&s.fields, |f| {
+            let field_opts = WrappedFieldOpts::from_field(f).expect("Wrong field options");
+            if field_opts.skip {
+                return None;
+            }
+            let name = &f.ident;
+            let name_str = name.as_ref().unwrap().to_string();
+
+            let is_already_option = is_option_type(&f.ty).is_some() && !is_tristate(f);
+            let should_process = *proc_usage_opts
+                .fields_to_wrap
+                .get(&name_str)
+                .unwrap_or(&true);
+            let w_name = field_opts.generated_ident(f);
+            if is_already_option || !should_process {
+                // Passthrough fields carry no "unset" state, so they must be equal
+                Some(quote! { { matches &= self.#name == other.#w_name; } })
+            } else {
+                Some(
+                    quote! { { matches &= other.#w_name.as_ref().is_none_or(|value| self.#name == *value); } },
+                )
+            }
+        });
+
+        quote! {
+            impl #impl_generics ::core::cmp::PartialEq<#wrapped_ident #mirror_ty_generics> for #original_ident #ty_generics #where_clause {
+                /// Whether every field set in the patch equals the same field of `self`.
+                ///
+                /// `None` wrapped fields match any value. Fields that are not wrapped, including
+                /// fields that were already `Option<T>`, are compared directly.
+                #[allow(unused_mut)]
+                fn eq(&self, other: &#wrapped_ident #mirror_ty_generics) -> bool {
+                    let mut matches = true;
+                    #(#checks)*
+                    matches
+                }
+            }
+        }
+    } else {
+        quote! {}
+    };
+
+    // Generate changed_fields - only fields this derive wrapped, where `Some` unambiguously means "set"
+    let changed_fields_helper = if opts.changed_fields {
+        let checks = gated_fields(&s.fields, |f| {
+            let field_opts = WrappedFieldOpts::from_field(f).expect("Wrong field options");
+            if field_opts.skip {
+                return None;
+            }
+            let name = &f.ident;
+            let name_str = name.as_ref().unwrap().to_string();
+
+            let is_already_option = is_option_type(&f.ty).is_some() && !is_tristate(f);
+            let should_process = *proc_usage_opts
+                .fields_to_wrap
+                .get(&name_str)
+                .unwrap_or(&true);
+            if is_already_option || !should_process {
+                return None;
+            }
+
+            let w_name = field_opts.generated_ident(f);
+            Some(quote! {
+                if self.#w_name.is_some() {
+                    changed.push(#name_str);
+                }
+            })
+        });
+
+        quote! {
+            impl #impl_generics #wrapped_ident #mirror_ty_generics #where_clause {
+                /// Names of the wrapped fields that are `Some`, in declaration order.
+                ///
+                /// Fields that were already `Option<T>` on the original struct are not listed.
+                #[allow(unused_mut)]
+                pub fn changed_fields(&self) -> ::std::vec::Vec<&'static str> {
+                    let mut changed = ::std::vec::Vec::new();
+                    #(#checks)*
+                    changed
+                }
+            }
+        }
+    } else {
+        quote! {}
+    };
+
+    // Generate iter_set - the fields `changed_fields` lists, each with its value as `&dyn Debug`
+    let iter_set_helper = if opts.iter_set {
+        let entries = gated_fields(&s.fields, |f| {
+            let field_opts = WrappedFieldOpts::from_field(f).expect("Wrong field options");
+            if field_opts.skip {
+                return None;
+            }
+            let name = &f.ident;
+            let name_str = name.as_ref().unwrap().to_string();
+
+            let is_already_option = is_option_type(&f.ty).is_some() && !is_tristate(f);
+            let should_process = *proc_usage_opts
+                .fields_to_wrap
+                .get(&name_str)
+                .unwrap_or(&true);
+            if is_already_option || !should_process {
+                return None;
+            }
+
+            let w_name = field_opts.generated_ident(f);
+            Some(quote! {
+                if let Some(value) = &self.#w_name {
+                    set.push((#name_str, value));
+                }
+            })
+        });
+
+        quote! {
+            impl #impl_generics #wrapped_ident #mirror_ty_generics #where_clause {
+                /// The wrapped fields that are `Some`, by name with their value, in declaration order.
+                ///
+                /// Fields that were already `Option<T>` on the original struct are not yielded.
+                #[allow(unused_mut)]
+                pub fn iter_set(&self) -> impl Iterator<Item = (&'static str, &dyn ::core::fmt::Debug)> {
+                    let mut set: ::std::vec::Vec<(&'static str, &dyn ::core::fmt::Debug)> = ::std::vec::Vec::new();
+                    #(#entries)*
+                    set.into_iter()
+                }
+            }
+        }
+    } else {
+        quote! {}
+    };
+
+    // Generate clear - non-`Option` passthrough fields have no empty value and keep theirs
+    let clear_helper = if opts.clearable {
+        let resets = gated_fields(&s.fields, |f| {
+            let field_opts = WrappedFieldOpts::from_field(f).expect("Wrong field options");
+            if field_opts.skip {
+                return None;
+            }
+            let name = &f.ident;
+            let name_str = name.as_ref().unwrap().to_string();
+
+            let is_already_option = is_option_type(&f.ty).is_some() && !is_tristate(f);
+            let should_process = *proc_usage_opts
+                .fields_to_wrap
+                .get(&name_str)
+                .unwrap_or(&true);
+            let w_name = field_opts.generated_ident(f);
+            (is_already_option || should_process).then(|| quote! { { self.#w_name = None; } })
+        });
+
+        quote! {
+            impl #impl_generics #wrapped_ident #mirror_ty_generics #where_clause {
+                /// Reset every `Option` field to `None`, so the patch can be reused.
+                ///
+                /// Fields that were not wrapped are not `Option`s and keep their current value.
+                pub fn clear(&mut self) {
+                    #(#resets)*
+                }
+            }
+        }
+    } else {
+        quote! {}
+    };
+
+    // Generate {Wrapped}Field - wrapped fields carry `T` and are stored as `Some`, other fields
+    // carry their type on the generated struct and are stored as-is
+    let field_enum_helper = if opts.field_enum {
+        let field_enum_ident = format_ident!("{}Field", wrapped_ident);
+        let (variants, assignments): (Vec<_>, Vec<_>) = s
+            .fields
+            .iter()
+            .filter_map(|f| {
+                let field_opts = WrappedFieldOpts::from_field(f).expect("Wrong field options");
+                if field_opts.skip {
+                    return None;
+                }
+                let name = f.ident.as_ref().unwrap();
+                let ty = &f.ty;
+                let name_str = name.to_string();
+                let w_name = field_opts.generated_ident(f);
+                let variant = snake_to_pascal_ident(&w_name);
+
+                let is_already_option = is_option_type(ty).is_some() && !is_tristate(f);
+                let should_process = *proc_usage_opts
+                    .fields_to_wrap
+                    .get(&name_str)
+                    .unwrap_or(&true);
+                let value = if is_already_option || !should_process {
+                    quote! { value }
+                } else {
+                    quote! { Some(value) }
+                };
+
+                let cfgs = cfg_attrs(&f.attrs);
+                Some((
+                    quote! { #(#cfgs)* #variant(#ty) },
+                    quote! { #(#cfgs)* #field_enum_ident::#variant(value) => self.#w_name = #value },
+                ))
+            })
+            .unzip();
+
+        quote! {
+            /// One field of the generated struct together with its new value.
+            #vis enum #field_enum_ident #mirror_impl_generics #mirror_where_clause {
+                #(#variants),*
+            }
+
+            impl #impl_generics #wrapped_ident #mirror_ty_generics #where_clause {
+                /// Assign a single field, wrapping the value in `Some` if the field was wrapped.
+                pub fn set(&mut self, field: #field_enum_ident #mirror_ty_generics) {
+                    match field {
+                        #(#assignments),*
+                    }
+                }
+            }
+        }
+    } else {
+        quote! {}
+    };
+
+    // Generate from_partial_with_defaults - missing wrapped fields use their `default`, skipped
+    // fields must have one since the patch never holds them
+    let defaults_fill_helper = if opts.defaults_fill {
+        let mut fill_fields = Vec::new();
+        for f in s.fields.iter() {
+            let field_opts = WrappedFieldOpts::from_field(f).expect("Wrong field options");
+            let name = &f.ident;
+            let name_str = name.as_ref().unwrap().to_string();
+            let cfgs = cfg_attrs(&f.attrs);
+
+            if field_opts.skip {
+                let Some(default) = &field_opts.default else {
+                    return syn::Error::new_spanned(
+                        f,
+                        "`defaults_fill` needs a `#[wrapped(default = ...)]` on skipped fields",
+                    )
+                    .to_compile_error();
+                };
+                fill_fields.push(quote! { #(#cfgs)* #name: #default });
+                continue;
+            }
+
+            let is_already_option = is_option_type(&f.ty).is_some() && !is_tristate(f);
+            let should_process = *proc_usage_opts
+                .fields_to_wrap
+                .get(&name_str)
+                .unwrap_or(&true);
+            let w_name = field_opts.generated_ident(f);
+            fill_fields.push(match &field_opts.default {
+                _ if is_already_option || !should_process => {
+                    quote! { #(#cfgs)* #name: partial.#w_name }
+                },
+                None if is_tristate(f) => quote! { #(#cfgs)* #name: partial.#w_name.flatten() },
+                Some(default) => {
+                    quote! { #(#cfgs)* #name: partial.#w_name.unwrap_or_else(|| #default) }
+                },
+                None => {
+                    let missing = missing_error(&lib_path, &name_str, &quote! { "" });
+                    quote! {
+                        #(#cfgs)*
+                        #name: partial.#w_name.ok_or(#missing)?
+                    }
+                },
+            });
+        }
+
+        quote! {
+            impl #impl_generics #wrapped_ident #mirror_ty_generics #where_clause {
+                /// Build the original struct, filling `None` fields with their `default`.
+                ///
+                /// Returns an error naming the first missing field that has no `default`.
+                pub fn from_partial_with_defaults(partial: Self) -> ::core::result::Result<#original_ident #ty_generics, #lib_path::UnwrappedError> {
+                    Ok(#original_ident {
+                        #(#fill_fields),*
+                    })
+                }
+            }
+        }
+    } else {
+        quote! {}
+    };
+
+    // Generate apply_all - fold every patch onto the base, later patches win
+    let apply_all_helper = if opts.apply_all {
+        let apply_fields = gated_fields(&s.fields, |f| {
+            let field_opts = WrappedFieldOpts::from_field(f).expect("Wrong field options");
+
+            // Skipped fields keep the base value
+            if field_opts.skip {
+                return None;
+            }
+            let name = &f.ident;
+            let ty = &f.ty;
+            let name_str = name.as_ref().unwrap().to_string();
+
+            let is_already_option = is_option_type(ty).is_some() && !is_tristate(f);
+            let should_process = *proc_usage_opts
+                .fields_to_wrap
+                .get(&name_str)
+                .unwrap_or(&true);
+
+            let w_name = field_opts.generated_ident(f);
+            if is_already_option {
+                Some(quote! {
+                    if patch.#w_name.is_some() {
+                        base.#name = patch.#w_name;
+                    }
+                })
+            } else if !should_process {
+                // Not wrapped, so every patch carries a value
+                Some(quote! { { base.#name = patch.#w_name; } })
+            } else {
+                Some(quote! {
+                    if let Some(value) = patch.#w_name {
+                        base.#name = value;
+                    }
+                })
+            }
+        });
+
+        quote! {
+            impl #impl_generics #wrapped_ident #mirror_ty_generics #where_clause {
+                /// Apply every patch onto `base`, from first to last.
+                ///
+                /// `Some` fields overwrite the current value, so later patches win. Fields that are
+                /// `None` in every patch keep the value from `base`.
+                pub fn apply_all(
+                    base: #original_ident #ty_generics,
+                    patches: impl IntoIterator<Item = Self>,
+                ) -> #original_ident #ty_generics {
+                    let mut base = base;
+                    for patch in patches {
+                        #(#apply_fields)*
+                    }
+                    base
+                }
+            }
+        }
+    } else {
+        quote! {}
+    };
+
+    // Generate merge_into - apply a single patch in place, keeping the base value for `None`
+    let merge_fields = gated_fields(&s.fields, |f| {
+        let field_opts = WrappedFieldOpts::from_field(f).expect("Wrong field options");
+
+        // Skipped fields keep the base value
+        if field_opts.skip {
+            return None;
+        }
+        let name = &f.ident;
+        let name_str = name.as_ref().unwrap().to_string();
+        let should_process = *proc_usage_opts
+            .fields_to_wrap
+            .get(&name_str)
+            .unwrap_or(&true);
+
+        let w_name = field_opts.generated_ident(f);
+        if is_option_type(&f.ty).is_some() && !is_tristate(f) {
+            if field_opts.merge_replace {
+                Some(quote! { { base.#name = self.#w_name; } })
+            } else {
+                Some(quote! {
+                    if self.#w_name.is_some() {
+                        base.#name = self.#w_name;
+                    }
+                })
+            }
+        } else if !should_process {
+            // Not wrapped, so the patch always carries a value
+            Some(quote! { { base.#name = self.#w_name; } })
+        } else {
+            Some(quote! {
+                if let Some(value) = self.#w_name {
+                    base.#name = value;
+                }
+            })
+        }
+    });
+    let merge_into_helper = quote! {
+        impl #impl_generics #wrapped_ident #mirror_ty_generics #where_clause {
+            /// Overwrite the fields of `base` that are `Some` in `self`, leaving the rest untouched.
+            ///
+            /// A field that is already `Option` in the original keeps its value when `None` here,
+            /// unless it is marked `merge_replace`, which clears it instead.
+            #[allow(unused_variables)]
+            pub fn merge_into(self, base: &mut #original_ident #ty_generics) {
+                #(#merge_fields)*
+            }
+        }
+    };
+
+    // Metadata for the trait impl
+    let field_count = field_count(s.fields.iter().filter(|f| {
+        !WrappedFieldOpts::from_field(f)
+            .expect("Wrong field options")
+            .skip
+    }));
+    let source_name = original_ident.to_string();
+
     // Build struct-level attributes and derives
-    let struct_attrs = &opts.struct_attrs;
+    let mut struct_attrs = opts.struct_attrs.clone();
+    if !common_opts.skips_attr("cfg_attr") {
+        struct_attrs.extend(forwarded_test_attrs(
+            &input.attrs,
+            derives_serde(&opts.struct_derives),
+        ));
+    }
+    let struct_attrs = &struct_attrs;
     let derive_output = build_derive_output(&opts.struct_derives);
-
+    let forwarded_attrs = forwarded_container_attrs(&input.attrs, &common_opts);
+    let float_warnings = float_derive_warnings(
+        &opts.struct_derives,
+        s.fields
+            .iter()
+            .filter(|f| {
+                !WrappedFieldOpts::from_field(f)
+                    .expect("Wrong field options")
+                    .skip
+            })
+            .map(|f| (f.ident.as_ref().unwrap().to_string(), &f.ty)),
+    );
+
+    // The generated struct and public helper types are emitted in the caller's module, every impl
+    // goes in an anonymous `const _` block so helper items never collide across derives
+    //
     // Only generate From implementations if there are no skipped fields
     if has_skipped_fields {
         // Collect skipped fields for into_original method
-        let skipped_params = s.fields.iter().filter_map(|f| {
+        let skipped_params = gated_fields(&s.fields, |f| {
             let field_opts = WrappedFieldOpts::from_field(f).expect("Wrong field options");
             if field_opts.skip {
                 let name = &f.ident;
@@ -318,7 +1335,7 @@ pub fn wrapped(
         });
 
         // Build field assignments for into_original
-        let into_original_fields = s.fields.iter().map(|f| {
+        let into_original_fields = gated_fields(&s.fields, |f| {
             let field_opts = WrappedFieldOpts::from_field(f).expect("Wrong field options");
             let name = &f.ident;
             let ty = &f.ty;
@@ -328,21 +1345,43 @@ pub fn wrapped(
                 // Skipped fields come from parameters
                 quote! { #name }
             } else {
-                let is_already_option = is_option_type(ty).is_some();
-                let should_process = *proc_usage_opts.fields_to_wrap.get(&name_str).unwrap_or(&true);
+                let is_already_option = is_option_type(ty).is_some() && !is_tristate(f);
+                let should_process = *proc_usage_opts
+                    .fields_to_wrap
+                    .get(&name_str)
+                    .unwrap_or(&true);
 
+                let w_name = field_opts.generated_ident(f);
                 if is_already_option || !should_process {
                     // Already Option or not processed -> keep as is
-                    quote! { #name: self.#name }
+                    quote! { #name: self.#w_name }
+                } else if is_tristate(f) {
+                    quote! { #name: self.#w_name.flatten() }
                 } else {
                     // Unwrap Option, return error if None
-                    let field_name_str = name.as_ref().unwrap().to_string();
-                    quote! { #name: self.#name.ok_or(::#lib_path::UnwrappedError{ field_name: #field_name_str })? }
+                    let missing = missing_error(
+                        &lib_path,
+                        &name.as_ref().unwrap().to_string(),
+                        &quote! { "" },
+                    );
+                    quote! { #name: self.#w_name.ok_or(#missing)? }
                 }
             }
         });
 
-        let builder_helper = if let Some(builder_info) = bon_builder_info(input) {
+        // A setter chain can't leave out a field behind `#[cfg(...)]`
+        let gates_fields = s.fields.iter().any(|f| {
+            !WrappedFieldOpts::from_field(f)
+                .expect("Wrong field options")
+                .skip
+                && !cfg_attrs(&f.attrs).is_empty()
+        });
+        let builder_info = if gates_fields {
+            None
+        } else {
+            bon_builder_info(input)
+        };
+        let builder_helper = if let Some(builder_info) = builder_info {
             let builder_ident = &builder_info.builder_ident;
             let state_mod_ident = &builder_info.state_mod_ident;
             let state_ident = unique_state_ident(&input.generics);
@@ -374,24 +1413,36 @@ pub fn wrapped(
                 let ty = &f.ty;
                 let name_str = name.to_string();
 
-                let is_already_option = is_option_type(ty).is_some();
+                let is_already_option = is_option_type(ty).is_some() && !is_tristate(f);
                 let should_process = *proc_usage_opts
                     .fields_to_wrap
                     .get(&name_str)
                     .unwrap_or(&true);
 
-                let (setter_ident, value) = if is_already_option {
-                    let maybe_name =
-                        syn::Ident::new(&format!("maybe_{}", raw_ident_name(name)), name.span());
-                    (maybe_name, quote! { w.#name })
+                // The setter follows the original field's shape in the builder, not the wrapped
+                // one: optional members (originally `Option<T>` or `#[builder(default)]`) take
+                // `maybe_` so a `None` is passed through, `#[builder(required)]` keeps an
+                // `Option<T>` on the plain setter, and everything else must be present.
+                let member = bon_member_config(&f.attrs);
+                let maybe_name =
+                    syn::Ident::new(&format!("maybe_{}", raw_ident_name(name)), name.span());
+                let w_name = field_opts.generated_ident(f);
+                let (setter_ident, value) = if is_tristate(f) {
+                    let setter_ident = if member.required {
+                        name.clone()
+                    } else {
+                        maybe_name
+                    };
+                    (setter_ident, quote! { w.#w_name.flatten() })
+                } else if is_already_option && member.required {
+                    (name.clone(), quote! { w.#w_name })
+                } else if is_already_option || (should_process && member.has_default) {
+                    (maybe_name, quote! { w.#w_name })
                 } else if !should_process {
-                    (name.clone(), quote! { w.#name })
+                    (name.clone(), quote! { w.#w_name })
                 } else {
-                    let field_name_str = name.to_string();
-                    (
-                        name.clone(),
-                        quote! { w.#name.ok_or(::#lib_path::UnwrappedError{ field_name: #field_name_str })? },
-                    )
+                    let missing = missing_error(&lib_path, &name.to_string(), &quote! { "" });
+                    (name.clone(), quote! { w.#w_name.ok_or(#missing)? })
                 };
 
                 setter_calls.push(quote! { .#setter_ident(#value) });
@@ -425,7 +1476,7 @@ pub fn wrapped(
                     /// Pre-fill the builder with the non-skipped fields from the wrapped struct.
                     ///
                     /// Returns an error if any required wrapped field is `None`.
-                    pub fn from_wrapped(self, w: #wrapped_ident #ty_generics) -> Result<#builder_return_ty, ::#lib_path::UnwrappedError>
+                    pub fn from_wrapped(self, w: #wrapped_ident #mirror_ty_generics) -> ::core::result::Result<#builder_return_ty, #lib_path::UnwrappedError>
                     #method_where
                     {
                         Ok(self #(#setter_calls)*)
@@ -439,59 +1490,119 @@ pub fn wrapped(
         quote! {
             #(#struct_attrs)*
             #derive_output
-            pub struct #wrapped_ident #ty_generics #where_clause {
+            #(#forwarded_attrs)*
+            #vis struct #wrapped_ident #mirror_impl_generics #mirror_where_clause {
                 #(#fields),*
             }
 
-            impl #impl_generics ::#lib_path::Wrapped for #original_ident #ty_generics #where_clause {
-                type Wrapped = #wrapped_ident #ty_generics;
-            }
+            #float_warnings
 
-            impl #impl_generics #wrapped_ident #ty_generics #where_clause {
-                /// Convert back to the original struct by providing values for skipped fields.
-                ///
-                /// This method takes the skipped fields as parameters and reconstructs
-                /// the original struct with non-skipped fields from `self`.
-                ///
-                /// Returns an error if any non-skipped wrapped field is `None`.
-                pub fn into_original(self, #(#skipped_params),*) -> Result<#original_ident #ty_generics, ::#lib_path::UnwrappedError> {
-                    Ok(#original_ident {
-                        #(#into_original_fields),*
-                    })
+            #field_enum_helper
+
+            const _: () = {
+                impl #impl_generics #lib_path::Wrapped for #original_ident #ty_generics #where_clause {
+                    type Wrapped = #wrapped_ident #mirror_ty_generics;
+
+                    const FIELD_COUNT: usize = #field_count;
+                    const SOURCE: &'static str = #source_name;
                 }
-            }
 
-            #builder_helper
+                impl #impl_generics #wrapped_ident #mirror_ty_generics #where_clause {
+                    /// Convert back to the original struct by providing values for skipped fields.
+                    ///
+                    /// This method takes the skipped fields as parameters and reconstructs
+                    /// the original struct with non-skipped fields from `self`.
+                    ///
+                    /// Returns an error if any non-skipped wrapped field is `None`.
+                    pub fn into_original(self, #(#skipped_params),*) -> ::core::result::Result<#original_ident #ty_generics, #lib_path::UnwrappedError> {
+                        Ok(#original_ident {
+                            #(#into_original_fields),*
+                        })
+                    }
+                }
+
+                #builder_helper
+
+                #diff_helper
+
+                #apply_all_helper
+
+                #merge_into_helper
+
+                #changed_fields_helper
+
+                #iter_set_helper
+
+                #cmp_helper
+
+                #clear_helper
+
+                #defaults_fill_helper
+            };
         }
     } else {
         quote! {
             #(#struct_attrs)*
             #derive_output
-            pub struct #wrapped_ident #ty_generics #where_clause {
+            #(#forwarded_attrs)*
+            #vis struct #wrapped_ident #mirror_impl_generics #mirror_where_clause {
                 #(#fields),*
             }
 
+            #float_warnings
 
+            #field_enum_helper
 
-            impl #impl_generics From<#original_ident #ty_generics> for #wrapped_ident #ty_generics #where_clause {
-                fn from(from: #original_ident #ty_generics) -> Self {
-                    Self {
-                        #(#to_wrapped_fields),*
+            const _: () = {
+                impl #impl_generics ::core::convert::From<#original_ident #ty_generics> for #wrapped_ident #mirror_ty_generics #where_clause {
+                    fn from(from: #original_ident #ty_generics) -> Self {
+                        Self {
+                            #(#to_wrapped_fields),*
+                        }
                     }
                 }
-            }
 
-            impl #impl_generics ::#lib_path::Wrapped for #original_ident #ty_generics #where_clause {
-                type Wrapped = #wrapped_ident #ty_generics;
-            }
+                impl #impl_generics #lib_path::Wrapped for #original_ident #ty_generics #where_clause {
+                    type Wrapped = #wrapped_ident #mirror_ty_generics;
 
-            impl #impl_generics #wrapped_ident #ty_generics #where_clause {
-                pub fn try_from(from: #wrapped_ident #ty_generics) -> Result<#original_ident #ty_generics, ::#lib_path::UnwrappedError> {
-                    Ok(#original_ident {
-                        #(#try_from_fields),*
-                    })
+                    const FIELD_COUNT: usize = #field_count;
+                    const SOURCE: &'static str = #source_name;
                 }
-            }
+
+                impl #impl_generics #wrapped_ident #mirror_ty_generics #where_clause {
+                    pub fn #try_from_ident(from: #wrapped_ident #mirror_ty_generics) -> ::core::result::Result<#original_ident #ty_generics, #lib_path::UnwrappedError> {
+                        Ok(#original_ident {
+                            #(#try_from_fields),*
+                        })
+                    }
+                }
+
+                impl #impl_generics ::core::convert::TryFrom<#wrapped_ident #mirror_ty_generics> for #original_ident #ty_generics #where_clause {
+                    type Error = #lib_path::UnwrappedError;
+
+                    fn try_from(from: #wrapped_ident #mirror_ty_generics) -> ::core::result::Result<Self, Self::Error> {
+                        #wrapped_ident::#try_from_ident(from)
+                    }
+                }
+
+                #completable_impl
+
+                #diff_helper
+
+                #apply_all_helper
+
+                #merge_into_helper
+
+                #changed_fields_helper
+
+                #iter_set_helper
+
+                #cmp_helper
+
+                #clear_helper
+
+                #defaults_fill_helper
+            };
         }
     }
 }
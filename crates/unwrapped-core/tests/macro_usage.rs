@@ -3,7 +3,7 @@ use std::collections::HashMap;
 use quote::{format_ident, quote};
 use syn::DeriveInput;
 use unwrapped_core::{
-    FieldProcOpts, Opts, UnwrappedFieldProcOpts, UnwrappedProcUsageOpts, WrappedOpts,
+    FieldContext, FieldProcOpts, Opts, UnwrappedFieldProcOpts, UnwrappedProcUsageOpts, WrappedOpts,
     WrappedProcUsageOpts, unwrapped, wrapped,
 };
 
@@ -34,8 +34,8 @@ fn test_macro_usage() {
     let expected = quote! {
         #[derive()]
         pub struct ThingFormValueHolder {
-            pub id: i32,
-            pub name: Option<String>
+            id: i32,
+            name: Option<String>
         }
     };
 
@@ -69,8 +69,8 @@ fn test_wrapped_macro_usage() {
     let expected = quote! {
         #[derive()]
         pub struct ThingFormValueHolder {
-            pub id: Option<i32>,
-            pub name: String
+            id: ::core::option::Option<i32>,
+            name: String
         }
     };
 
@@ -375,3 +375,981 @@ fn test_wrapped_with_field_attr_fn() {
     let output = model_struct.to_string();
     assert!(output.contains("primary_key"));
 }
+
+#[test]
+fn test_unwrapped_with_derive_form_field_attrs() {
+    let thing = quote! {
+        #[unwrapped(derive(Clone, PartialEq), attr(repr(C)))]
+        struct Thing {
+            #[unwrapped(attr(serde(skip), doc = "identifier"))]
+            id: Option<i32>,
+            name: Option<String>,
+        }
+    };
+
+    let parsed: DeriveInput = syn::parse2(thing).unwrap();
+
+    let model_struct = unwrapped(&parsed, None, UnwrappedProcUsageOpts::default());
+
+    let expected = quote! {
+        #[repr(C)]
+        #[derive(Clone, PartialEq)]
+        pub struct ThingUw {
+            #[serde(skip)]
+            #[doc = "identifier"]
+            id: i32,
+            name: String
+        }
+    };
+
+    assert!(model_struct.to_string().contains(&expected.to_string()));
+}
+
+#[test]
+fn test_wrapped_with_derive_form_field_attrs() {
+    let thing = quote! {
+        #[wrapped(derive(Clone, PartialEq), attr(repr(C)))]
+        struct Thing {
+            #[wrapped(attr(serde(skip), doc = "identifier"))]
+            id: i32,
+            name: String,
+        }
+    };
+
+    let parsed: DeriveInput = syn::parse2(thing).unwrap();
+
+    let model_struct = wrapped(&parsed, None, WrappedProcUsageOpts::default());
+
+    let expected = quote! {
+        #[repr(C)]
+        #[derive(Clone, PartialEq)]
+        pub struct ThingW {
+            #[serde(skip)]
+            #[doc = "identifier"]
+            id: ::core::option::Option<i32>,
+            name: ::core::option::Option<String>
+        }
+    };
+
+    assert!(model_struct.to_string().contains(&expected.to_string()));
+}
+
+#[test]
+fn test_unwrapped_with_crate_lib_holder() {
+    let thing = quote! {
+        struct Thing {
+            id: Option<i32>,
+        }
+    };
+
+    let parsed: DeriveInput = syn::parse2(thing).unwrap();
+
+    for holder in ["crate", "self"] {
+        let macro_options =
+            UnwrappedProcUsageOpts::new(HashMap::new(), Some(format_ident!("{}", holder).into()));
+        let output = unwrapped(&parsed, None, macro_options).to_string();
+
+        assert!(output.contains(&quote! { impl crate::Unwrapped for Thing }.to_string()));
+        assert!(output.contains(&quote! { crate::UnwrappedError }.to_string()));
+        assert!(!output.contains(":: unwrapped"));
+    }
+}
+
+#[test]
+fn test_wrapped_with_crate_attribute() {
+    let thing = quote! {
+        #[wrapped(crate = "crate")]
+        struct Thing {
+            id: i32,
+        }
+    };
+
+    let parsed: DeriveInput = syn::parse2(thing).unwrap();
+
+    let output = wrapped(&parsed, None, WrappedProcUsageOpts::default()).to_string();
+
+    assert!(output.contains(&quote! { impl crate::Wrapped for Thing }.to_string()));
+    assert!(output.contains(&quote! { crate::UnwrappedError }.to_string()));
+}
+
+#[test]
+fn test_unwrapped_with_lib_holder() {
+    let thing = quote! {
+        struct Thing {
+            id: Option<i32>,
+        }
+    };
+
+    let parsed: DeriveInput = syn::parse2(thing).unwrap();
+
+    let macro_options =
+        UnwrappedProcUsageOpts::new(HashMap::new(), Some(format_ident!("my_macros").into()));
+    let output = unwrapped(&parsed, None, macro_options).to_string();
+
+    assert!(output.contains(&quote! { ::my_macros::unwrapped::UnwrappedError }.to_string()));
+}
+
+#[test]
+fn test_inherit_vis() {
+    let thing = quote! {
+        #[unwrapped(inherit_vis)]
+        #[wrapped(inherit_vis)]
+        pub(in crate::models) struct Thing {
+            id: Option<i32>,
+        }
+    };
+
+    let parsed: DeriveInput = syn::parse2(thing).unwrap();
+
+    let unwrapped_output = unwrapped(&parsed, None, UnwrappedProcUsageOpts::default()).to_string();
+    assert!(
+        unwrapped_output.contains(&quote! { pub(in crate::models) struct ThingUw }.to_string())
+    );
+
+    let wrapped_output = wrapped(&parsed, None, WrappedProcUsageOpts::default()).to_string();
+    assert!(wrapped_output.contains(&quote! { pub(in crate::models) struct ThingW }.to_string()));
+
+    // Without the option the mirror stays `pub`
+    let parsed: DeriveInput = syn::parse2(quote! {
+        pub(crate) struct Thing {
+            id: Option<i32>,
+        }
+    })
+    .unwrap();
+    let output = unwrapped(&parsed, None, UnwrappedProcUsageOpts::default()).to_string();
+    assert!(output.contains(&quote! { pub struct ThingUw }.to_string()));
+}
+
+#[test]
+fn test_with_lib_holder() {
+    let thing = quote! {
+        struct Thing {
+            id: Option<i32>,
+        }
+    };
+
+    let parsed: DeriveInput = syn::parse2(thing).unwrap();
+
+    let unwrapped_options =
+        UnwrappedProcUsageOpts::default().with_lib_holder(format_ident!("my_macros"));
+    let output = unwrapped(&parsed, None, unwrapped_options).to_string();
+    assert!(output.contains(&quote! { ::my_macros::unwrapped::UnwrappedError }.to_string()));
+
+    let wrapped_options =
+        WrappedProcUsageOpts::default().with_lib_holder(format_ident!("my_macros"));
+    let output = wrapped(&parsed, None, wrapped_options).to_string();
+    assert!(
+        output.contains(&quote! { impl ::my_macros::unwrapped::Wrapped for Thing }.to_string())
+    );
+}
+
+#[test]
+fn test_with_lib_holder_path() {
+    let thing = quote! {
+        struct Thing {
+            id: Option<i32>,
+        }
+    };
+
+    let parsed: DeriveInput = syn::parse2(thing).unwrap();
+    let vendored: syn::Path = syn::parse_quote!(crate::vendored::uw);
+
+    let unwrapped_options = UnwrappedProcUsageOpts::default().with_lib_holder(vendored.clone());
+    let output = unwrapped(&parsed, None, unwrapped_options).to_string();
+    assert!(output.contains(&quote! { impl crate::vendored::uw::Unwrapped for Thing }.to_string()));
+    assert!(output.contains(&quote! { crate::vendored::uw::UnwrappedError }.to_string()));
+    assert!(!output.contains("unwrapped :: UnwrappedError"));
+
+    let wrapped_options = WrappedProcUsageOpts::new(HashMap::new(), Some(vendored));
+    let output = wrapped(&parsed, None, wrapped_options).to_string();
+    assert!(output.contains(&quote! { impl crate::vendored::uw::Wrapped for Thing }.to_string()));
+}
+
+#[test]
+fn test_const_generic_params_are_declared() {
+    let thing = quote! {
+        struct Thing<const N: usize> {
+            bytes: Option<[u8; N]>,
+        }
+    };
+
+    let parsed: DeriveInput = syn::parse2(thing).unwrap();
+
+    let output = unwrapped(&parsed, None, UnwrappedProcUsageOpts::default()).to_string();
+    assert!(output.contains(&quote! { pub struct ThingUw<const N: usize> }.to_string()));
+
+    let output = wrapped(&parsed, None, WrappedProcUsageOpts::default()).to_string();
+    assert!(output.contains(&quote! { pub struct ThingW<const N: usize> }.to_string()));
+}
+
+#[test]
+fn test_with_field_opts_bulk() {
+    let thing = quote! {
+        struct Thing {
+            id: Option<i32>,
+            name: Option<String>,
+        }
+    };
+
+    let parsed: DeriveInput = syn::parse2(thing).unwrap();
+
+    let macro_options = UnwrappedProcUsageOpts::default().with_field_opts_bulk([
+        (
+            "id".to_string(),
+            UnwrappedFieldProcOpts::new(true).with_attr(quote! { #[allow(unused)] }),
+        ),
+        (
+            "name".to_string(),
+            UnwrappedFieldProcOpts::new(true).with_attr(quote! { #[allow(dead_code)] }),
+        ),
+        // Later entries override earlier ones
+        (
+            "id".to_string(),
+            UnwrappedFieldProcOpts::new(true).with_attr(quote! { #[doc = "id"] }),
+        ),
+    ]);
+    let output = unwrapped(&parsed, None, macro_options).to_string();
+
+    assert!(output.contains(&quote! { #[doc = "id"] id: i32 }.to_string()));
+    assert!(output.contains(&quote! { #[allow(dead_code)] name: String }.to_string()));
+    assert!(!output.contains(&quote! { #[allow(unused)] }.to_string()));
+
+    let macro_options = WrappedProcUsageOpts::default().with_field_opts_bulk(vec![(
+        "id",
+        FieldProcOpts::new(true).with_attr(quote! { #[allow(unused)] }),
+    )]);
+    let output = wrapped(&parsed, None, macro_options).to_string();
+
+    assert!(output.contains(&quote! { #[allow(unused)] id: Option<i32> }.to_string()));
+}
+
+#[test]
+fn test_serde_with_forwarded_only_with_serde_derives() {
+    let thing = quote! {
+        struct Thing {
+            #[serde(rename = "ts", with = "ts_seconds")]
+            created: Option<i64>,
+            #[serde(serialize_with = "ser", deserialize_with = "de")]
+            updated: i64,
+        }
+    };
+
+    let parsed: DeriveInput = syn::parse2(thing).unwrap();
+
+    let output = unwrapped(&parsed, None, UnwrappedProcUsageOpts::default()).to_string();
+    assert!(!output.contains("serde"));
+
+    let model_options = Opts::builder()
+        .build()
+        .with_derives(quote! { serde::Serialize, serde::Deserialize });
+    let output = unwrapped(
+        &parsed,
+        Some(model_options),
+        UnwrappedProcUsageOpts::default(),
+    )
+    .to_string();
+
+    assert!(output.contains(&quote! { #[serde(with = "ts_seconds")] created: i64 }.to_string()));
+    assert!(
+        output.contains(
+            &quote! { #[serde(serialize_with = "ser", deserialize_with = "de")] updated: i64 }
+                .to_string()
+        )
+    );
+}
+
+#[test]
+fn test_unwrapped_only_rejects_unknown_fields() {
+    let thing = quote! {
+        #[unwrapped(only("id", "nmae"))]
+        struct Thing {
+            id: Option<i32>,
+            name: Option<String>,
+        }
+    };
+
+    let parsed: DeriveInput = syn::parse2(thing).unwrap();
+
+    let output = unwrapped(&parsed, None, UnwrappedProcUsageOpts::default()).to_string();
+    assert!(output.contains("compile_error"));
+    assert!(output.contains("no field named `nmae`"));
+}
+
+#[test]
+fn test_wrapped_nullable_overrides_fields_to_wrap() {
+    let thing = quote! {
+        #[wrapped(nullable("name"))]
+        struct Thing {
+            id: i32,
+            name: String,
+        }
+    };
+
+    let parsed: DeriveInput = syn::parse2(thing).unwrap();
+
+    let mut fields_to_wrap: HashMap<String, bool> = HashMap::new();
+    fields_to_wrap.insert("id".to_owned(), true);
+    let macro_options = WrappedProcUsageOpts::new(fields_to_wrap, None);
+    let output = wrapped(&parsed, None, macro_options).to_string();
+
+    assert!(output.contains(&quote! { id: i32, name: ::core::option::Option<String> }.to_string()));
+
+    let thing = quote! {
+        #[wrapped(nullable("nmae"))]
+        struct Thing {
+            name: String,
+        }
+    };
+
+    let parsed: DeriveInput = syn::parse2(thing).unwrap();
+
+    let output = wrapped(&parsed, None, WrappedProcUsageOpts::default()).to_string();
+    assert!(output.contains("no field named `nmae`"));
+}
+
+#[test]
+fn test_unwrapped_reinterpret_requires_identical_repr_c_layout() {
+    let missing_repr = quote! {
+        #[unwrapped(reinterpret)]
+        struct Thing {
+            id: i32,
+        }
+    };
+    let parsed: DeriveInput = syn::parse2(missing_repr).unwrap();
+    let output = unwrapped(&parsed, None, UnwrappedProcUsageOpts::default()).to_string();
+    assert!(output.contains("requires `#[repr(C)]`"));
+
+    let unwrapped_field = quote! {
+        #[unwrapped(reinterpret)]
+        #[repr(C, align(8))]
+        struct Thing {
+            id: Option<i32>,
+        }
+    };
+    let parsed: DeriveInput = syn::parse2(unwrapped_field).unwrap();
+    let output = unwrapped(&parsed, None, UnwrappedProcUsageOpts::default()).to_string();
+    assert!(output.contains("requires every field to be kept unchanged"));
+
+    // `Box<Option<T>>` becomes `Box<T>`, the same size but a different pointee
+    let boxed_field = quote! {
+        #[unwrapped(reinterpret)]
+        #[repr(C)]
+        struct Thing {
+            next: Box<Option<[u64; 4]>>,
+        }
+    };
+    let parsed: DeriveInput = syn::parse2(boxed_field).unwrap();
+    let output = unwrapped(&parsed, None, UnwrappedProcUsageOpts::default()).to_string();
+    assert!(output.contains("requires every field to be kept unchanged"));
+    assert!(!output.contains("as_original"));
+
+    let identical = quote! {
+        #[unwrapped(reinterpret)]
+        #[repr(C)]
+        struct Thing {
+            id: i32,
+        }
+    };
+    let parsed: DeriveInput = syn::parse2(identical).unwrap();
+    let output = unwrapped(&parsed, None, UnwrappedProcUsageOpts::default()).to_string();
+    assert!(output.contains(&quote! { #[repr(C)] #[derive()] pub struct ThingUw }.to_string()));
+    assert!(output.contains("as_original"));
+}
+
+#[test]
+fn test_unwrapped_presence_mask_rejects_more_than_64_fields() {
+    let fields = (0..65).map(|i| quote::format_ident!("f{i}"));
+    let input = quote! {
+        #[unwrapped(presence_mask)]
+        struct Wide {
+            #(#fields: Option<u8>,)*
+        }
+    };
+    let parsed: DeriveInput = syn::parse2(input).unwrap();
+    let output = unwrapped(&parsed, None, UnwrappedProcUsageOpts::default()).to_string();
+    assert!(output.contains("at most 64 non-skipped fields, found 65"));
+}
+
+#[test]
+fn test_unwrapped_strict_rejects_unwrapped_skip_deserializing() {
+    let input = quote! {
+        #[unwrapped(strict)]
+        struct Session {
+            #[serde(skip_deserializing)]
+            hits: Option<u32>,
+        }
+    };
+    let parsed: DeriveInput = syn::parse2(input).unwrap();
+    let output = unwrapped(&parsed, None, UnwrappedProcUsageOpts::default()).to_string();
+    assert!(output.contains("`skip_deserializing` field is unwrapped"));
+
+    // Skipping the field keeps its `Option`, so strict accepts it
+    let input = quote! {
+        #[unwrapped(strict)]
+        struct Session {
+            #[serde(skip_deserializing)]
+            #[unwrapped(skip)]
+            hits: Option<u32>,
+        }
+    };
+    let parsed: DeriveInput = syn::parse2(input).unwrap();
+    let output = unwrapped(&parsed, None, UnwrappedProcUsageOpts::default()).to_string();
+    assert!(!output.contains("compile_error"));
+}
+
+#[test]
+fn test_test_cfg_attrs_forwarded_without_own_attrs() {
+    let input = quote! {
+        #[cfg_attr(test, derive(Debug, Unwrapped), unwrapped(derive(Clone)))]
+        #[cfg_attr(feature = "x", derive(Hash))]
+        struct Thing {
+            #[cfg_attr(test, doc = "test only", serde(rename = "n"))]
+            name: Option<String>,
+        }
+    };
+    let parsed: DeriveInput = syn::parse2(input).unwrap();
+    let output = unwrapped(&parsed, None, UnwrappedProcUsageOpts::default()).to_string();
+
+    assert!(output.contains(&quote! { #[cfg_attr(test, derive(Debug))] }.to_string()));
+    assert!(
+        output.contains(&quote! { #[cfg_attr(test, doc = "test only")] name: String }.to_string())
+    );
+    // Only `cfg_attr(test, ...)` is forwarded, and `serde` is dropped without a serde derive
+    assert!(!output.contains("Hash"));
+    assert!(!output.contains("rename"));
+}
+
+#[test]
+fn test_with_option_like() {
+    let thing = quote! {
+        struct Thing {
+            id: Maybe<i32>,
+            name: Option<String>,
+        }
+    };
+
+    let parsed: DeriveInput = syn::parse2(thing).unwrap();
+
+    let options = UnwrappedProcUsageOpts::default()
+        .with_option_like(format_ident!("Maybe"), format_ident!("into_result"));
+    let output = unwrapped(&parsed, None, options).to_string();
+
+    assert!(output.contains(&quote! { pub struct ThingUw { id: i32, name: String } }.to_string()));
+    assert!(output.contains(
+        &quote! { id: from.id.into_result().map_err(|err| ::unwrapped::UnwrappedError { field_name: ::std::borrow::Cow::Borrowed("id"), source_field: "", kind: ::unwrapped::UnwrappedErrorKind::conversion(err), })? }
+            .to_string()
+    ));
+    assert!(output.contains(&quote! { id: ::core::convert::From::from(from.id) }.to_string()));
+
+    // Disabling the field keeps the wrapper
+    let options = UnwrappedProcUsageOpts::new(HashMap::from([("id".to_string(), false)]), None)
+        .with_option_like(format_ident!("Maybe"), format_ident!("into_result"));
+    let output = unwrapped(&parsed, None, options).to_string();
+    assert!(output.contains(&quote! { id: Maybe<i32> }.to_string()));
+}
+
+#[test]
+fn test_duplicate_derives_are_emitted_once() {
+    let thing = quote! {
+        struct Thing {
+            id: Option<i32>,
+        }
+    };
+    let parsed: DeriveInput = syn::parse2(thing).unwrap();
+
+    let model_options = Opts::builder()
+        .struct_derives(vec![quote! { Clone }, quote! { Debug }])
+        .build()
+        .with_derive(quote! { ::core::clone::Clone })
+        .with_derives(quote! { Debug, PartialEq });
+    let output = unwrapped(
+        &parsed,
+        Some(model_options),
+        UnwrappedProcUsageOpts::default(),
+    );
+
+    assert!(
+        output
+            .to_string()
+            .contains(&quote! { #[derive(Clone, Debug, PartialEq)] }.to_string())
+    );
+}
+
+#[test]
+fn test_tuple_struct_keys_by_position() {
+    let thing = quote! {
+        struct Pair(Option<i32>, Option<String>);
+    };
+    let parsed: DeriveInput = syn::parse2(thing).unwrap();
+
+    let model_options = Opts::builder()
+        .build()
+        .with_field_attr("1", quote! { #[doc = "second"] });
+    let options = UnwrappedProcUsageOpts::new(HashMap::from([("0".to_string(), false)]), None);
+    let output = unwrapped(&parsed, Some(model_options), options).to_string();
+
+    assert!(output.contains(
+        &quote! { pub struct PairUw(Option<i32>, #[doc = "second"] String); }.to_string()
+    ));
+    assert!(output.contains(
+        &quote! { 1: from.1.ok_or(::unwrapped::UnwrappedError { field_name: ::std::borrow::Cow::Borrowed("1"), source_field: "", kind: ::unwrapped::UnwrappedErrorKind::Missing, })? }
+            .to_string()
+    ));
+}
+
+#[test]
+fn test_float_fields_warn_on_eq_and_hash() {
+    let thing = quote! {
+        struct Thing {
+            ratio: Option<f64>,
+            count: Option<u32>,
+        }
+    };
+    let parsed: DeriveInput = syn::parse2(thing).unwrap();
+
+    let model_options = Opts::builder()
+        .build()
+        .with_derives(quote! { PartialEq, Eq, ::core::hash::Hash });
+    let output = unwrapped(
+        &parsed,
+        Some(model_options),
+        UnwrappedProcUsageOpts::default(),
+    )
+    .to_string();
+
+    assert!(output.contains(
+        &quote! { #[deprecated(note = "field `ratio` has a float type, which does not implement `Eq`, `Hash` derived on the generated struct")] }
+            .to_string()
+    ));
+    assert_eq!(output.matches("deprecated").count(), 1);
+
+    // Floats are fine without `Eq`/`Hash`/`Ord`
+    let model_options = Opts::builder().build().with_derive(quote! { PartialEq });
+    let output = unwrapped(
+        &parsed,
+        Some(model_options),
+        UnwrappedProcUsageOpts::default(),
+    )
+    .to_string();
+    assert!(!output.contains("deprecated"));
+}
+
+#[test]
+fn test_impls_emitted_in_anonymous_const() {
+    let thing = quote! {
+        struct Thing {
+            id: Option<i32>,
+            name: String
+        }
+    };
+    let parsed: DeriveInput = syn::parse2(thing).unwrap();
+
+    let unwrapped_output = unwrapped(
+        &parsed,
+        Some(Opts::builder().build()),
+        UnwrappedProcUsageOpts::default(),
+    )
+    .to_string();
+    let wrapped_output = wrapped(
+        &parsed,
+        Some(WrappedOpts::builder().build()),
+        WrappedProcUsageOpts::default(),
+    )
+    .to_string();
+
+    let anonymous_const = quote! { const _: () = }.to_string();
+    for output in [unwrapped_output, wrapped_output] {
+        let (outside, inside) = output.split_once(&anonymous_const).unwrap();
+        assert!(outside.contains("pub struct"));
+        assert!(!outside.contains("impl"));
+        assert!(inside.contains("impl"));
+    }
+}
+
+#[test]
+fn test_field_doc_comments_forwarded() {
+    let thing = quote! {
+        struct Thing {
+            /// The identifier
+            id: Option<i32>,
+        }
+    };
+    let parsed: DeriveInput = syn::parse2(thing).unwrap();
+
+    let model_options = Opts::builder()
+        .build()
+        .with_field_attr("id", quote! { #[serde(rename = "ID")] });
+    let output = unwrapped(
+        &parsed,
+        Some(model_options),
+        UnwrappedProcUsageOpts::default(),
+    )
+    .to_string();
+    // Docs come before the collected field attributes
+    assert!(output.contains(
+        &quote! { #[doc = r" The identifier"] #[serde(rename = "ID")] id: i32 }.to_string()
+    ));
+
+    let output = wrapped(
+        &parsed,
+        Some(WrappedOpts::builder().no_docs(true).build()),
+        WrappedProcUsageOpts::default(),
+    )
+    .to_string();
+    assert!(!output.contains("The identifier"));
+}
+
+#[test]
+fn test_forward_and_skip_attrs() {
+    let thing = quote! {
+        #[diesel(table_name = users)]
+        struct User {
+            /// The identifier
+            #[diesel(column_name = user_id)]
+            id: Option<i32>,
+        }
+    };
+    let parsed: DeriveInput = syn::parse2(thing).unwrap();
+
+    // Unknown namespaces are dropped by default
+    let output = unwrapped(
+        &parsed,
+        Some(Opts::builder().build()),
+        UnwrappedProcUsageOpts::default(),
+    )
+    .to_string();
+    assert!(!output.contains("diesel"));
+    assert!(output.contains("The identifier"));
+
+    let model_options = WrappedOpts::builder()
+        .forward_attrs(vec![syn::parse_quote!("diesel")])
+        .skip_attrs(vec![syn::parse_quote!("doc")])
+        .build();
+    let output = wrapped(
+        &parsed,
+        Some(model_options),
+        WrappedProcUsageOpts::default(),
+    )
+    .to_string();
+    assert!(
+        output.contains(&quote! { #[diesel(table_name = users)] pub struct UserW }.to_string())
+    );
+    assert!(
+        output.contains(&quote! { #[diesel(column_name = user_id)] id: Option<i32> }.to_string())
+    );
+    assert!(!output.contains("The identifier"));
+}
+
+#[test]
+fn test_cfg_fields_gate_generated_code() {
+    let thing = quote! {
+        struct Thing {
+            id: Option<i32>,
+            #[cfg(feature = "extra")]
+            extra: Option<u8>,
+        }
+    };
+    let parsed: DeriveInput = syn::parse2(thing).unwrap();
+
+    let output = unwrapped(&parsed, None, UnwrappedProcUsageOpts::default()).to_string();
+    assert!(output.contains(
+        &quote! { pub struct ThingUw { id: i32, #[cfg(feature = "extra")] extra: u8 } }.to_string()
+    ));
+    assert!(
+        output.contains(&quote! { #[cfg(feature = "extra")] extra: Some(from.extra) }.to_string())
+    );
+    assert!(
+        output.contains(&quote! { #[cfg(feature = "extra")] extra: from.extra.ok_or }.to_string())
+    );
+
+    let output = wrapped(&parsed, None, WrappedProcUsageOpts::default()).to_string();
+    assert!(output.contains(
+        &quote! { pub struct ThingW { id: Option<i32>, #[cfg(feature = "extra")] extra: Option<u8> } }
+            .to_string()
+    ));
+    assert!(output.contains(&quote! { #[cfg(feature = "extra")] extra: from.extra }.to_string()));
+}
+
+#[test]
+fn test_unwrapped_smart_default_rejects_unknown_types() {
+    let thing = quote! {
+        #[unwrapped(smart_default)]
+        struct Thing {
+            name: Option<String>,
+            port: Option<Port>,
+        }
+    };
+
+    let parsed: DeriveInput = syn::parse2(thing).unwrap();
+
+    let output = unwrapped(&parsed, None, UnwrappedProcUsageOpts::default()).to_string();
+    assert!(output.contains("compile_error"));
+    assert!(output.contains("`smart_default` cannot tell that this type implements `Default`"));
+
+    // A field-level default makes any type acceptable
+    let thing = quote! {
+        #[unwrapped(smart_default)]
+        struct Thing {
+            name: Option<String>,
+            #[unwrapped(default = Port(8080))]
+            port: Option<Port>,
+        }
+    };
+
+    let parsed: DeriveInput = syn::parse2(thing).unwrap();
+
+    let output = unwrapped(&parsed, None, UnwrappedProcUsageOpts::default()).to_string();
+    assert!(!output.contains("compile_error"));
+    assert!(output.contains("Default for ThingUw"));
+}
+
+#[test]
+fn test_with_field_attr_fn_ctx() {
+    let thing = quote! {
+        struct Thing {
+            id: Option<i32>,
+            #[unwrapped(skip)]
+            secret: String,
+            name: String,
+        }
+    };
+
+    fn attr_generator(ctx: &FieldContext<'_>) -> Option<proc_macro2::TokenStream> {
+        let rename = format!("{}_{}", ctx.struct_ident, ctx.index);
+        let transformed = ctx.transformed;
+        Some(quote! { #[column(rename = #rename, transformed = #transformed)] })
+    }
+
+    let parsed: DeriveInput = syn::parse2(thing).unwrap();
+
+    let output = unwrapped(
+        &parsed,
+        None,
+        UnwrappedProcUsageOpts::default().with_field_attr_fn_ctx(attr_generator),
+    )
+    .to_string();
+    assert!(output.contains(
+        &quote! { #[column(rename = "Thing_0", transformed = true)] id: i32 }.to_string()
+    ));
+    // Positions count skipped fields
+    assert!(output.contains(
+        &quote! { #[column(rename = "Thing_2", transformed = false)] name: String }.to_string()
+    ));
+
+    let output = wrapped(
+        &parsed,
+        None,
+        WrappedProcUsageOpts::default().with_field_attr_fn_ctx(attr_generator),
+    )
+    .to_string();
+    assert!(output.contains(
+        &quote! { #[column(rename = "Thing_0", transformed = false)] id: Option<i32> }.to_string()
+    ));
+    assert!(
+        output.contains(
+            &quote! { #[column(rename = "Thing_2", transformed = true)] name: ::core::option::Option<String> }
+                .to_string()
+        )
+    );
+}
+
+#[cfg(feature = "zeroize")]
+#[test]
+fn test_unwrapped_zeroize_places_skip_attrs() {
+    let thing = quote! {
+        #[unwrapped(zeroize)]
+        struct Thing {
+            secret: Option<String>,
+            #[unwrapped(zeroize_skip)]
+            name: Option<String>,
+        }
+    };
+
+    let parsed: DeriveInput = syn::parse2(thing).unwrap();
+
+    let output = unwrapped(&parsed, None, UnwrappedProcUsageOpts::default()).to_string();
+    assert!(output.contains(&quote! { #[derive(::zeroize::Zeroize)] }.to_string()));
+    assert!(output.contains(&quote! { secret: String, #[zeroize(skip)] name: String }.to_string()));
+}
+
+#[cfg(not(feature = "zeroize"))]
+#[test]
+fn test_unwrapped_zeroize_requires_feature() {
+    let thing = quote! {
+        #[unwrapped(zeroize)]
+        struct Thing {
+            secret: Option<String>,
+        }
+    };
+
+    let parsed: DeriveInput = syn::parse2(thing).unwrap();
+
+    let output = unwrapped(&parsed, None, UnwrappedProcUsageOpts::default()).to_string();
+    assert!(output.contains("`zeroize` requires the `zeroize` feature of `unwrapped`"));
+}
+
+#[test]
+fn test_unwrapped_downcast_requires_dyn_any() {
+    let thing = quote! {
+        struct Thing {
+            #[unwrapped(downcast)]
+            state: Option<Box<String>>,
+        }
+    };
+
+    let parsed: DeriveInput = syn::parse2(thing).unwrap();
+
+    let output = unwrapped(&parsed, None, UnwrappedProcUsageOpts::default()).to_string();
+    assert!(
+        output.contains("`downcast` needs a field that is `Box<dyn Any>` on the generated struct")
+    );
+}
+
+#[test]
+fn test_unwrapped_with_requires_ty() {
+    let thing = quote! {
+        struct Thing {
+            #[unwrapped(with = parse_port)]
+            port: Option<String>,
+        }
+    };
+
+    let parsed: DeriveInput = syn::parse2(thing).unwrap();
+
+    let output = unwrapped(&parsed, None, UnwrappedProcUsageOpts::default()).to_string();
+    assert!(output.contains("`with` needs the type it converts to, e.g. `ty = u16`"));
+}
+
+#[test]
+fn test_unwrapped_builder_rejects_derived_builder() {
+    let thing = quote! {
+        #[unwrapped(builder, derive(bon::Builder))]
+        struct Thing {
+            id: Option<u32>,
+        }
+    };
+
+    let parsed: DeriveInput = syn::parse2(thing).unwrap();
+
+    let output = unwrapped(&parsed, None, UnwrappedProcUsageOpts::default()).to_string();
+    assert!(output.contains("`builder` generates `ThingUwBuilder`, which collides with the builder of the forwarded `derive(Builder)`"));
+}
+
+#[test]
+fn test_with_where() {
+    let thing = quote! {
+        struct Thing<T> {
+            value: Option<T>,
+        }
+    };
+    let parsed: DeriveInput = syn::parse2(thing).unwrap();
+
+    let model_options = Opts::builder()
+        .build()
+        .with_derive(quote! { ::serde::Serialize })
+        .with_where(quote! { T: ::serde::Serialize });
+    let output = unwrapped(
+        &parsed,
+        Some(model_options),
+        UnwrappedProcUsageOpts::default(),
+    )
+    .to_string();
+
+    assert!(output.contains(
+        &quote! { pub struct ThingUw<T> where T: ::serde::Serialize { value: T } }.to_string()
+    ));
+    assert!(output.contains(
+        &quote! { impl<T> ::core::convert::TryFrom<Thing<T> > for ThingUw<T> where T: ::serde::Serialize }
+            .to_string()
+    ));
+
+    let model_options = WrappedOpts::builder()
+        .build()
+        .with_where(quote! { T: ::core::fmt::Debug });
+    let output = wrapped(
+        &parsed,
+        Some(model_options),
+        WrappedProcUsageOpts::default(),
+    )
+    .to_string();
+
+    assert!(
+        output.contains(
+            &quote! { pub struct ThingW<T> where T: ::core::fmt::Debug { value: Option<T> } }
+                .to_string()
+        )
+    );
+    assert!(output.contains(
+        &quote! { impl<T> ::core::convert::From<Thing<T> > for ThingW<T> where T: ::core::fmt::Debug }
+            .to_string()
+    ));
+}
+
+#[test]
+fn test_enum_rejects_struct_only_options() {
+    let event = quote! {
+        #[unwrapped(view)]
+        #[wrapped(diff)]
+        enum Event {
+            Click { x: Option<i32> },
+        }
+    };
+
+    let parsed: DeriveInput = syn::parse2(event).unwrap();
+
+    let output = unwrapped(&parsed, None, UnwrappedProcUsageOpts::default()).to_string();
+    assert!(output.contains("`view` is not supported on enums"));
+    let output = wrapped(&parsed, None, WrappedProcUsageOpts::default()).to_string();
+    assert!(output.contains("`diff` is not supported on enums"));
+
+    let event = quote! {
+        enum Event {
+            Click {
+                #[unwrapped(assert = "value > 0")]
+                #[wrapped(default = 0)]
+                x: Option<i32>,
+            },
+        }
+    };
+
+    let parsed: DeriveInput = syn::parse2(event).unwrap();
+
+    let output = unwrapped(&parsed, None, UnwrappedProcUsageOpts::default()).to_string();
+    assert!(output.contains("`assert` is not supported on enum variant fields"));
+    let output = wrapped(&parsed, None, WrappedProcUsageOpts::default()).to_string();
+    assert!(output.contains("`default` is not supported on enum variant fields"));
+}
+
+#[test]
+fn test_enum_variant_field_attrs() {
+    let event = quote! {
+        enum Event {
+            Click {
+                /// Horizontal position
+                #[diesel(column_name = pos_x)]
+                x: Option<i32>,
+            },
+            Key(u32),
+        }
+    };
+
+    let parsed: DeriveInput = syn::parse2(event).unwrap();
+
+    let model_options = Opts::builder()
+        .forward_attrs(vec![syn::parse_quote!("diesel")])
+        .build()
+        .with_field_attr("x", quote! { #[allow(unused)] });
+    let output = unwrapped(
+        &parsed,
+        Some(model_options),
+        UnwrappedProcUsageOpts::default()
+            .with_field_attr_fn(|f| f.ident.is_none().then(|| quote! { #[doc = "key"] })),
+    )
+    .to_string();
+
+    assert!(output.contains(
+        &quote! { #[doc = r" Horizontal position"] #[diesel(column_name = pos_x)] #[allow(unused)] x: i32 }
+            .to_string()
+    ));
+    assert!(output.contains(&quote! { Key(#[doc = "key"] u32) }.to_string()));
+}
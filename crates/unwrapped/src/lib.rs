@@ -1,25 +1,142 @@
+use std::borrow::Cow;
+use std::sync::Arc;
+
 #[doc = include_str!("../README.md")]
-/// Error returned by `try_from()` when an `Option` field is `None`.
+/// Error returned by `try_from()` when a field can't be converted.
 ///
-/// Contains the name of the field that failed to unwrap, useful for debugging
-/// and error reporting.
-#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+/// Contains the name of the field that failed to unwrap and why, useful for
+/// debugging and error reporting.
+#[derive(Clone, Debug, Eq, PartialEq)]
 pub struct UnwrappedError {
     /// The name of the field that was `None`.
-    pub field_name: &'static str,
+    ///
+    /// Borrowed for a field of the converted struct itself, owned when the name
+    /// is composed at runtime, e.g. `"server.port"` for a nested field.
+    pub field_name: Cow<'static, str>,
+    /// The conversion that observed the `None`, e.g. `"UserUw::try_from"`.
+    ///
+    /// Only set in debug builds of structs using `#[unwrapped(provenance)]`,
+    /// empty otherwise.
+    pub source_field: &'static str,
+    /// Why the field failed.
+    pub kind: UnwrappedErrorKind,
 }
 
 impl std::fmt::Display for UnwrappedError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(
-            f,
-            "Failed to unwrap an Option for field '{}', found None",
-            self.field_name
-        )
+        match &self.kind {
+            UnwrappedErrorKind::Missing => write!(
+                f,
+                "Failed to unwrap an Option for field '{}', found None",
+                self.field_name
+            )?,
+            UnwrappedErrorKind::Assertion => {
+                write!(f, "Assertion failed for field '{}'", self.field_name)?
+            },
+            UnwrappedErrorKind::Conversion(err) => {
+                write!(f, "Failed to convert field '{}': {}", self.field_name, err)?
+            },
+        }
+        if !self.source_field.is_empty() {
+            write!(f, " in {}", self.source_field)?;
+        }
+        Ok(())
     }
 }
 
-impl std::error::Error for UnwrappedError {}
+impl std::error::Error for UnwrappedError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match &self.kind {
+            UnwrappedErrorKind::Conversion(err) => Some(&**err),
+            UnwrappedErrorKind::Missing | UnwrappedErrorKind::Assertion => None,
+        }
+    }
+}
+
+/// Lets conversions that cannot fail stand in for ones that can.
+///
+/// With `#[unwrapped(default_from)]`, `TryFrom<Original>` comes from the blanket impl over
+/// `From` and fails with `Infallible`, which a `recursive` field of that type converts here.
+impl From<std::convert::Infallible> for UnwrappedError {
+    fn from(never: std::convert::Infallible) -> Self {
+        match never {}
+    }
+}
+
+/// Why a field failed to convert, see `UnwrappedError::kind`.
+#[derive(Clone, Debug)]
+pub enum UnwrappedErrorKind {
+    /// The field was `None`.
+    Missing,
+    /// The field's `#[unwrapped(assert = "...")]` check failed.
+    Assertion,
+    /// The field's `with` function or `Option`-like extraction returned this error.
+    Conversion(Arc<dyn std::error::Error + Send + Sync>),
+}
+
+impl UnwrappedErrorKind {
+    /// Wrap the error returned by a field's `with` function or `Option`-like extraction.
+    pub fn conversion(err: impl Into<Box<dyn std::error::Error + Send + Sync>>) -> Self {
+        Self::Conversion(Arc::from(err.into()))
+    }
+}
+
+/// Conversion errors compare by their message, since `dyn Error` has no equality.
+impl PartialEq for UnwrappedErrorKind {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::Missing, Self::Missing) | (Self::Assertion, Self::Assertion) => true,
+            (Self::Conversion(a), Self::Conversion(b)) => a.to_string() == b.to_string(),
+            _ => false,
+        }
+    }
+}
+
+impl Eq for UnwrappedErrorKind {}
+
+/// Errors returned by `try_from_all()`, one per field that was `None`.
+///
+/// Errors are in field declaration order and the list is never empty.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct UnwrappedErrors(pub Vec<UnwrappedError>);
+
+impl std::fmt::Display for UnwrappedErrors {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let names: Vec<String> = self
+            .0
+            .iter()
+            .map(|err| format!("'{}'", err.field_name))
+            .collect();
+        match names.split_last() {
+            None => write!(f, "Failed to unwrap Option fields"),
+            Some((last, [])) => write!(
+                f,
+                "Failed to unwrap an Option for field {}, found None",
+                last
+            ),
+            Some((last, rest)) => write!(
+                f,
+                "Failed to unwrap Options for fields {} and {}, found None",
+                rest.join(", "),
+                last
+            ),
+        }
+    }
+}
+
+impl std::error::Error for UnwrappedErrors {}
+
+/// Presence of a field in an original struct, as reported by the generated `analyze()`.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Presence {
+    /// The field is unwrapped, or a `when_tag` field its tag requires, and holds `Some`.
+    Present,
+    /// The field is unwrapped, or a `when_tag` field its tag requires, and holds `None`, so
+    /// `try_from()` would fail on it.
+    Missing,
+    /// The field is carried over as-is, so `try_from()` never fails on it.
+    NotOptional,
+}
 
 /// Trait that associates a struct with its unwrapped variant.
 ///
@@ -28,6 +145,12 @@ impl std::error::Error for UnwrappedError {}
 pub trait Unwrapped {
     /// The unwrapped variant of this type.
     type Unwrapped;
+
+    /// Number of fields in the unwrapped variant, `0` unless overridden.
+    const FIELD_COUNT: usize = 0;
+
+    /// Name of the original struct, empty unless overridden.
+    const SOURCE: &'static str = "";
 }
 
 /// Trait that associates a struct with its wrapped variant.
@@ -37,6 +160,26 @@ pub trait Unwrapped {
 pub trait Wrapped {
     /// The wrapped variant of this type.
     type Wrapped;
+
+    /// Number of fields in the wrapped variant, `0` unless overridden.
+    const FIELD_COUNT: usize = 0;
+
+    /// Name of the original struct, empty unless overridden.
+    const SOURCE: &'static str = "";
+}
+
+/// Trait for partial types that can be completed into a full value.
+///
+/// Automatically implemented by `#[derive(Wrapped)]` for the generated struct
+/// when no field is skipped, completing it into the original struct. The
+/// original must be `pub` or use `#[wrapped(inherit_vis)]`, since the impl
+/// names it as `Complete`.
+pub trait Completable {
+    /// The complete type produced by `complete()`.
+    type Complete;
+
+    /// Complete this value, failing on the first required field that is `None`.
+    fn complete(self) -> Result<Self::Complete, UnwrappedError>;
 }
 
 #[cfg(feature = "derive")]
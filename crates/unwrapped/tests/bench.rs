@@ -0,0 +1,26 @@
+#![cfg(feature = "bench")]
+
+use unwrapped::Unwrapped;
+
+#[derive(Debug, PartialEq, Unwrapped)]
+#[unwrapped(derive(Debug, PartialEq), bench)]
+struct Order {
+    id: Option<u64>,
+    note: Option<String>,
+    urgent: bool,
+}
+
+#[test]
+fn bench_try_from_matches_try_from() {
+    let make = || Order {
+        id: Some(7),
+        note: Some("fragile".to_string()),
+        urgent: true,
+    };
+
+    assert_eq!(
+        OrderUw::__bench_try_from(make()).unwrap(),
+        OrderUw::try_from(make()).unwrap()
+    );
+    assert!(OrderUw::__bench_try_from(Order { id: None, ..make() }).is_err());
+}
@@ -0,0 +1,24 @@
+#![cfg(feature = "proptest")]
+
+use proptest::prelude::*;
+use unwrapped::Unwrapped;
+
+#[derive(Debug, PartialEq, Unwrapped)]
+#[unwrapped(derive(Clone, Debug, PartialEq), arbitrary)]
+struct Reading {
+    sensor: Option<String>,
+    value: Option<i64>,
+    calibrated: bool,
+}
+
+proptest! {
+    #[test]
+    fn test_arbitrary_round_trip(uw in any::<ReadingUw>()) {
+        let original: Reading = uw.clone().into();
+        prop_assert_eq!(original.sensor.as_ref(), Some(&uw.sensor));
+        prop_assert_eq!(original.value, Some(uw.value));
+
+        let back = ReadingUw::try_from(original).unwrap();
+        prop_assert_eq!(back, uw);
+    }
+}
@@ -1,4 +1,8 @@
-use unwrapped::{Unwrapped, Wrapped};
+#![allow(clippy::bool_assert_comparison)]
+
+use unwrapped::{
+    Completable, Presence, Unwrapped, UnwrappedError, UnwrappedErrorKind, UnwrappedErrors, Wrapped,
+};
 
 #[test]
 fn test_unwrapped_from_no_defaults() {
@@ -167,6 +171,58 @@ fn test_struct_with_no_options() {
     assert_eq!(converted_back, original);
 }
 
+#[test]
+fn test_unwrapped_into_original_without_skipped_fields() {
+    #[derive(Clone, Debug, PartialEq, Unwrapped)]
+    #[unwrapped(derive(Clone))]
+    struct Settings {
+        name: Option<String>,
+        level: u8,
+    }
+
+    let original = Settings {
+        name: Some("dev".to_string()),
+        level: 2,
+    };
+
+    // Nothing is skipped, so `into_original` takes no parameters and agrees with `From`
+    let uw = SettingsUw::try_from(original.clone()).unwrap();
+    assert_eq!(uw.clone().into_original(), original);
+    assert_eq!(Settings::from(uw), original);
+}
+
+#[test]
+fn test_unwrapped_transparent() {
+    #[derive(Debug, Clone, PartialEq, Unwrapped)]
+    #[unwrapped(transparent)]
+    pub struct NoOptions<'a, T, const N: usize>
+    where
+        T: Clone,
+    {
+        a: &'a [T; N],
+        b: bool,
+    }
+
+    // Nothing to unwrap, so the generated name is an alias of the original
+    let original = NoOptions {
+        a: &[1u8, 2],
+        b: true,
+    };
+    let uw: NoOptionsUw<'_, u8, 2> = original.clone();
+    assert_eq!(uw, original);
+    assert_eq!(<NoOptions<'_, u8, 2> as Unwrapped>::FIELD_COUNT, 2);
+
+    // An `Option` field changes the shape, so a real struct is generated
+    #[derive(Debug, Clone, PartialEq, Unwrapped)]
+    #[unwrapped(transparent)]
+    pub struct WithOption {
+        a: Option<i32>,
+    }
+
+    let uw = WithOptionUw::try_from(WithOption { a: Some(1) }).unwrap();
+    assert_eq!(uw.a, 1);
+}
+
 #[test]
 fn test_skip_field() {
     #[derive(Debug, PartialEq, Unwrapped)]
@@ -313,242 +369,2810 @@ fn test_skip_field_with_bon_builder_pattern() {
     assert_eq!(original2.id, 999);
 }
 
-// ==================== Wrapped Tests ====================
-
 #[test]
-fn test_wrapped_simple_struct() {
-    #[derive(Debug, PartialEq, Wrapped)]
-    struct Simple {
-        field1: i32,
-        field2: String,
-        field3: Option<u64>,
+fn test_unwrapped_bon_builder_with_into_setters() {
+    // `on(..., into)` turns setters into `impl Into<T>`, the helper must still pass values that
+    // resolve to the field type
+    #[derive(Debug, Clone, PartialEq, Unwrapped)]
+    #[unwrapped(derive(Debug, Clone, PartialEq))]
+    pub struct Port {
+        number: Option<u16>,
     }
 
-    let original = Simple {
-        field1: 10,
-        field2: "hello".to_string(),
-        field3: Some(100),
-    };
+    #[derive(bon::Builder, Debug, PartialEq, Unwrapped)]
+    #[builder(on(_, into))]
+    struct Server {
+        name: Option<String>,
+        label: String,
+        #[unwrapped(recursive)]
+        port: Option<Port>,
+        #[unwrapped(skip)]
+        id: u64,
+    }
 
-    // Convert to wrapped - non-Option fields become Option
-    let wrapped = SimpleW::from(original);
-    assert_eq!(wrapped.field1, Some(10));
-    assert_eq!(wrapped.field2, Some("hello".to_string()));
-    assert_eq!(wrapped.field3, Some(100)); // Already Option, stays as-is
+    let uw = ServerUw {
+        name: "web".to_string(),
+        label: "primary".to_string(),
+        port: PortUw { number: 80 },
+    };
+    let server = Server::builder().from_unwrapped(uw).id(7u32).build();
 
-    // Convert back
-    let converted_back: Simple = SimpleW::try_from(wrapped).unwrap();
-    assert_eq!(converted_back.field1, 10);
-    assert_eq!(converted_back.field2, "hello".to_string());
-    assert_eq!(converted_back.field3, Some(100));
+    assert_eq!(
+        server,
+        Server {
+            name: Some("web".to_string()),
+            label: "primary".to_string(),
+            port: Some(Port { number: Some(80) }),
+            id: 7,
+        }
+    );
 }
 
 #[test]
-fn test_wrapped_with_none_values() {
-    #[derive(Debug, PartialEq, Wrapped)]
-    struct WithOptionals {
-        required: i32,
-        optional: String,
+fn test_unwrapped_derive_form_derives_and_attrs() {
+    #[derive(Debug, PartialEq, Unwrapped)]
+    #[unwrapped(derive(Clone, Debug, PartialEq), attr(allow(dead_code)))]
+    struct Profile {
+        #[unwrapped(attr(allow(unused)))]
+        nickname: Option<String>,
+        age: u8,
     }
 
-    let wrapped = WithOptionalsW {
-        required: Some(42),
-        optional: Some("value".to_string()),
-    };
+    let unwrapped = ProfileUw::try_from(Profile {
+        nickname: Some("neo".to_string()),
+        age: 30,
+    })
+    .unwrap();
 
-    // Conversion from wrapped requires all fields to be Some (no defaults!)
-    let converted: WithOptionals = WithOptionalsW::try_from(wrapped).unwrap();
-    assert_eq!(converted.required, 42);
-    assert_eq!(converted.optional, "value".to_string());
+    // The generated struct derives the requested traits
+    assert_eq!(unwrapped.clone(), unwrapped);
+    assert_eq!(
+        format!("{:?}", unwrapped),
+        "ProfileUw { nickname: \"neo\", age: 30 }"
+    );
 }
 
 #[test]
-fn test_wrapped_try_from() {
-    #[derive(Debug, PartialEq, Wrapped)]
-    struct Config {
-        timeout: u64,
-        retries: i32,
-        name: String,
+fn test_unwrapped_tagged_payloads() {
+    #[derive(Debug, PartialEq)]
+    enum Kind {
+        Text,
+        Image,
+        Ping,
     }
 
-    let wrapped_all_some = ConfigW {
-        timeout: Some(30),
-        retries: Some(3),
-        name: Some("test".to_string()),
-    };
+    #[derive(Debug, PartialEq, Unwrapped)]
+    #[unwrapped(tag = kind)]
+    struct Message {
+        id: Option<u64>,
+        kind: Kind,
+        #[unwrapped(when_tag = Text)]
+        text: Option<String>,
+        #[unwrapped(when_tag = Image)]
+        url: Option<String>,
+        #[unwrapped(when_tag = Text, when_tag = Image)]
+        sender: Option<String>,
+    }
 
-    // try_from should succeed when all fields are Some
-    let result = ConfigW::try_from(wrapped_all_some);
-    assert!(result.is_ok());
-    let config = result.unwrap();
-    assert_eq!(config.timeout, 30);
-    assert_eq!(config.retries, 3);
-    assert_eq!(config.name, "test".to_string());
+    // Tagged payloads stay optional on the generated struct
+    let text = MessageUw::try_from(Message {
+        id: Some(1),
+        kind: Kind::Text,
+        text: Some("hello".to_string()),
+        url: None,
+        sender: Some("neo".to_string()),
+    })
+    .unwrap();
+    assert_eq!(text.id, 1);
+    assert_eq!(text.text, Some("hello".to_string()));
+    assert_eq!(text.url, None);
 
-    let wrapped_missing = ConfigW {
-        timeout: Some(30),
-        retries: None,
-        name: Some("test".to_string()),
-    };
+    // Payloads unrelated to the tag may be missing
+    let ping = MessageUw::try_from(Message {
+        id: Some(2),
+        kind: Kind::Ping,
+        text: None,
+        url: None,
+        sender: None,
+    });
+    assert!(ping.is_ok());
 
-    // try_from should fail when any field is None
-    let result = ConfigW::try_from(wrapped_missing);
-    assert!(result.is_err());
-    assert_eq!(result.unwrap_err().field_name, "retries");
+    // Payloads required by the tag must be present
+    let image = MessageUw::try_from(Message {
+        id: Some(3),
+        kind: Kind::Image,
+        text: None,
+        url: None,
+        sender: Some("trinity".to_string()),
+    });
+    assert_eq!(image.err().unwrap().field_name, "url");
+
+    let image = MessageUw::try_from(Message {
+        id: Some(4),
+        kind: Kind::Image,
+        text: None,
+        url: Some("https://example.com/a.png".to_string()),
+        sender: None,
+    });
+    assert_eq!(image.err().unwrap().field_name, "sender");
 }
 
 #[test]
-fn test_wrapped_with_generics() {
-    #[derive(Clone, Debug, PartialEq, Wrapped)]
-    struct Generic<T: Clone + PartialEq + std::fmt::Debug + Default> {
-        value: T,
-        id: i32,
-    }
+fn test_unwrapped_transparent_newtype() {
+    // No Clone, Default, or other bounds on the newtype
+    #[derive(Debug, PartialEq)]
+    #[repr(transparent)]
+    struct UserId(u64);
 
-    let original = Generic {
-        value: true,
-        id: 123,
-    };
+    #[derive(Debug, PartialEq, Unwrapped)]
+    struct Session {
+        user: Option<UserId>,
+        parent: Option<UserId>,
+        token: String,
+    }
 
-    let wrapped = GenericW::from(original);
-    assert_eq!(wrapped.value, Some(true));
-    assert_eq!(wrapped.id, Some(123));
+    let unwrapped = SessionUw::try_from(Session {
+        user: Some(UserId(7)),
+        parent: Some(UserId(1)),
+        token: "abc".to_string(),
+    })
+    .unwrap();
+    assert_eq!(unwrapped.user, UserId(7));
+    assert_eq!(unwrapped.user.0, 7);
 
-    let converted_back: Generic<bool> = GenericW::try_from(wrapped).unwrap();
-    assert_eq!(converted_back.value, true);
-    assert_eq!(converted_back.id, 123);
+    let original: Session = unwrapped.into();
+    assert_eq!(
+        original,
+        Session {
+            user: Some(UserId(7)),
+            parent: Some(UserId(1)),
+            token: "abc".to_string(),
+        }
+    );
 }
 
 #[test]
-fn test_wrapped_trait() {
-    #[derive(Debug, PartialEq, Wrapped)]
-    struct MyStruct {
-        data: String,
+fn test_unwrapped_string_visitor() {
+    #[derive(Debug, PartialEq, Unwrapped)]
+    #[unwrapped(string_visitor)]
+    struct Contact {
+        name: Option<String>,
+        email: String,
+        age: Option<u8>,
+        #[unwrapped(skip)]
+        internal: String,
     }
 
-    fn check_wrapped<T: Wrapped<Wrapped = W>, W>(_: T) {}
-    check_wrapped(MyStruct {
-        data: "test".to_string(),
-    });
+    let mut contact = ContactUw::try_from(Contact {
+        name: Some("  Alice ".to_string()),
+        email: " ALICE@EXAMPLE.COM".to_string(),
+        age: Some(30),
+        internal: "ignored".to_string(),
+    })
+    .unwrap();
+
+    contact.for_each_string_field(|value| *value = value.trim().to_lowercase());
+
+    assert_eq!(contact.name, "alice");
+    assert_eq!(contact.email, "alice@example.com");
+    assert_eq!(contact.age, 30);
 }
 
 #[test]
-fn test_wrapped_skip_field() {
-    #[derive(Debug, PartialEq, Wrapped)]
-    #[wrapped(name = SkippedW)]
-    #[allow(dead_code)]
-    struct Skipped {
-        field_a: u32,
-        #[wrapped(skip)]
-        field_b: Option<String>,
-        field_c: bool,
-    }
+fn test_unwrapped_downcast() {
+    use std::any::Any;
 
-    // With skip, field_b is removed from the generated struct
-    // SkippedW only has field_a and field_c wrapped in Option
-    let wrapped = SkippedW {
-        field_a: Some(10),
-        field_c: Some(true),
-    };
-    assert_eq!(wrapped.field_a, Some(10));
-    assert_eq!(wrapped.field_c, Some(true));
+    #[derive(Unwrapped)]
+    struct Plugin {
+        name: Option<String>,
+        #[unwrapped(downcast)]
+        state: Option<Box<dyn Any>>,
+        #[unwrapped(downcast)]
+        shared: Box<dyn Any + Send + Sync>,
+    }
 
-    // Verify we can construct with None values too
-    let wrapped2 = SkippedW {
-        field_a: Some(20),
-        field_c: None,
-    };
-    assert_eq!(wrapped2.field_a, Some(20));
-    assert_eq!(wrapped2.field_c, None);
+    let plugin = PluginUw::try_from(Plugin {
+        name: Some("counter".to_string()),
+        state: Some(Box::new(42u32)),
+        shared: Box::new("config".to_string()),
+    })
+    .unwrap();
 
-    // Note: From implementations are NOT generated when skip is used
-    // because we can't convert between structs with different field counts.
+    assert_eq!(plugin.name, "counter");
+    assert_eq!(plugin.state_as::<u32>(), Some(&42));
+    assert_eq!(plugin.state_as::<i64>(), None);
+    assert_eq!(
+        plugin.shared_as::<String>().map(String::as_str),
+        Some("config")
+    );
 }
 
 #[test]
-fn test_wrapped_with_custom_name() {
-    #[derive(Debug, PartialEq, Wrapped)]
-    #[wrapped(prefix = "A", name = UserWrapped, suffix = c)]
-    struct User0;
-
-    #[allow(dead_code)]
-    type Works0 = AUserWrappedc;
+fn test_unwrapped_expect() {
+    #[derive(Debug, PartialEq, Unwrapped)]
+    #[unwrapped(expect)]
+    struct Config {
+        host: Option<String>,
+        port: Option<u16>,
+    }
 
-    #[derive(Debug, PartialEq, Wrapped)]
-    #[wrapped(prefix = Bad)]
-    struct User1;
+    let uw = ConfigUw::expect_unwrapped(Config {
+        host: Some("localhost".to_string()),
+        port: Some(8080),
+    });
+    assert_eq!(uw.host, "localhost");
+    assert_eq!(uw.port, 8080);
 
-    #[allow(dead_code)]
-    type Works1 = BadUser1;
+    let result = std::panic::catch_unwind(|| {
+        ConfigUw::expect_unwrapped(Config {
+            host: Some("localhost".to_string()),
+            port: None,
+        })
+    });
+    let payload = result.err().unwrap();
+    let message = payload.downcast_ref::<String>().unwrap();
+    assert!(message.contains("Config"));
+    assert!(message.contains("port"));
+}
 
-    #[derive(Debug, PartialEq, Wrapped)]
-    #[wrapped(suffix = "Something")]
-    struct User2;
+#[test]
+fn test_unwrapped_split() {
+    #[derive(Clone, Debug, PartialEq, Unwrapped)]
+    #[unwrapped(derive(Debug, PartialEq), split, method_name = "validate")]
+    struct Config<T> {
+        host: Option<String>,
+        tags: Option<Vec<T>>,
+    }
 
-    #[allow(dead_code)]
-    type Works2 = User2Something;
+    let config = Config {
+        host: Some("localhost".to_string()),
+        tags: Some(vec![1u8]),
+    };
+    let (original, mut uw) = ConfigUw::try_split(config.clone()).unwrap();
+    assert_eq!(original, config);
+    assert_eq!(
+        uw,
+        ConfigUw {
+            host: "localhost".to_string(),
+            tags: vec![1],
+        }
+    );
 
-    #[derive(Debug, PartialEq, Wrapped)]
-    #[wrapped(prefix = Bad, suffix = Something)]
-    struct User3;
+    // Both halves are independent
+    uw.tags.push(2);
+    assert_eq!(original.tags, Some(vec![1]));
 
-    #[allow(dead_code)]
-    type Works3 = BadUser3Something;
+    let err = ConfigUw::<u8>::try_split(Config {
+        host: None,
+        tags: None,
+    })
+    .unwrap_err();
+    assert_eq!(err.field_name, "host");
 }
 
 #[test]
-fn test_wrapped_skip_field_into_original() {
-    #[derive(Debug, PartialEq, Wrapped)]
-    #[wrapped(name = ConfigW)]
-    struct Config {
-        timeout: u64,
-        retries: i32,
-        #[wrapped(skip)]
-        created_at: i64,
-        #[wrapped(skip)]
-        version: String,
-    }
-
-    // Create a wrapped struct (without skipped fields)
-    let wrapped = ConfigW {
-        timeout: Some(30),
-        retries: Some(3),
-    };
+fn test_unwrapped_borrow() {
+    // Only the non-skipped fields need `Clone`
+    #[derive(Debug, PartialEq)]
+    struct Connection(u32);
 
-    // Convert back to original using into_original, providing skipped fields
-    let original = wrapped
-        .into_original(1234567890, "v1.0".to_string())
-        .unwrap();
+    #[derive(Debug, PartialEq, Unwrapped)]
+    #[unwrapped(derive(Debug, PartialEq), borrow, tag = kind)]
+    struct Order<'a> {
+        kind: OrderKind,
+        id: Option<u32>,
+        label: Option<&'a str>,
+        items: Box<Option<Vec<String>>>,
+        #[unwrapped(when_tag = Express)]
+        courier: Option<String>,
+        #[unwrapped(skip)]
+        connection: Connection,
+    }
 
-    assert_eq!(original.timeout, 30);
-    assert_eq!(original.retries, 3);
-    assert_eq!(original.created_at, 1234567890);
-    assert_eq!(original.version, "v1.0".to_string());
+    #[derive(Clone, Debug, PartialEq)]
+    enum OrderKind {
+        Standard,
+        Express,
+    }
 
-    // Test error case when wrapped field is None
-    let wrapped_none = ConfigW {
-        timeout: None,
-        retries: Some(5),
+    let order = Order {
+        kind: OrderKind::Standard,
+        id: Some(1),
+        label: Some("gift"),
+        items: Box::new(Some(vec!["book".to_string()])),
+        courier: None,
+        connection: Connection(7),
     };
 
-    let result = wrapped_none.into_original(9999999999, "v2.0".to_string());
-    assert!(result.is_err());
-    assert_eq!(result.unwrap_err().field_name, "timeout");
+    // The original is only borrowed, so it can be converted again
+    let first = order.try_as_unwrapped().unwrap();
+    let second = order.try_as_unwrapped().unwrap();
+    assert_eq!(first, second);
+    assert_eq!(
+        first,
+        OrderUw {
+            kind: OrderKind::Standard,
+            id: 1,
+            label: "gift",
+            items: Box::new(vec!["book".to_string()]),
+            courier: None,
+        }
+    );
+    assert_eq!(order.connection, Connection(7));
 
-    // Test with manually constructed wrapped struct
-    let wrapped2 = ConfigW {
-        timeout: Some(60),
-        retries: Some(10),
+    let express = Order {
+        kind: OrderKind::Express,
+        ..order
     };
-
-    // Convert back with skipped field values
-    let reconstructed = wrapped2
-        .into_original(2222222222, "v4.0".to_string())
-        .unwrap();
-    assert_eq!(reconstructed.timeout, 60);
-    assert_eq!(reconstructed.retries, 10);
-    assert_eq!(reconstructed.created_at, 2222222222);
-    assert_eq!(reconstructed.version, "v4.0".to_string());
+    assert_eq!(
+        express.try_as_unwrapped().unwrap_err().field_name,
+        "courier"
+    );
+}
+
+#[test]
+fn test_unwrapped_skip_default_from() {
+    #[derive(Debug, PartialEq, Unwrapped)]
+    #[unwrapped(skip_default)]
+    struct Record {
+        name: Option<String>,
+        #[unwrapped(skip)]
+        created_at: u64,
+        #[unwrapped(skip)]
+        tags: Vec<String>,
+    }
+
+    let uw = RecordUw {
+        name: "Alice".to_string(),
+    };
+
+    let original: Record = uw.into();
+    assert_eq!(
+        original,
+        Record {
+            name: Some("Alice".to_string()),
+            created_at: 0,
+            tags: Vec::new(),
+        }
+    );
+
+    // into_original is still available for explicit values
+    let uw = RecordUw {
+        name: "Bob".to_string(),
+    };
+    let original = uw.into_original(42, vec!["admin".to_string()]);
+    assert_eq!(original.created_at, 42);
+}
+
+#[test]
+fn test_unwrapped_skip_with_field_default() {
+    #[derive(Debug, PartialEq, Unwrapped)]
+    struct Draft {
+        title: Option<String>,
+        #[unwrapped(skip, default)]
+        views: u32,
+        #[unwrapped(skip)]
+        author_id: u64,
+    }
+
+    // Only the manually skipped field is a parameter
+    let original = DraftUw {
+        title: "hello".to_string(),
+    }
+    .into_original(9);
+    assert_eq!(
+        original,
+        Draft {
+            title: Some("hello".to_string()),
+            views: 0,
+            author_id: 9,
+        }
+    );
+
+    #[derive(Debug, PartialEq, Unwrapped)]
+    struct Note {
+        body: Option<String>,
+        #[unwrapped(skip, default)]
+        pinned: Option<bool>,
+        #[unwrapped(skip, default = 1)]
+        revision: u32,
+    }
+
+    // Every skipped field defaults, so `From` is generated again
+    let original: Note = NoteUw {
+        body: "hi".to_string(),
+    }
+    .into();
+    assert_eq!(
+        original,
+        Note {
+            body: Some("hi".to_string()),
+            pinned: None,
+            revision: 1,
+        }
+    );
+}
+
+#[test]
+fn test_unwrapped_bridge_to_wrapped() {
+    #[derive(Debug, PartialEq, Unwrapped, Wrapped)]
+    #[unwrapped(bridge, derive(Debug, PartialEq))]
+    #[wrapped(derive(Debug, PartialEq))]
+    #[allow(dead_code)]
+    struct Account {
+        name: Option<String>,
+        age: u32,
+        #[unwrapped(skip)]
+        note: String,
+        #[unwrapped(skip)]
+        #[wrapped(skip)]
+        id: u64,
+    }
+
+    let w = AccountW {
+        name: Some("Alice".to_string()),
+        age: Some(30),
+        note: Some("ignored".to_string()),
+    };
+    let uw: AccountUw = w.try_into().unwrap();
+    assert_eq!(
+        uw,
+        AccountUw {
+            name: "Alice".to_string(),
+            age: 30,
+        }
+    );
+
+    // Fields skipped only by Unwrapped come back as None
+    let w: AccountW = uw.into();
+    assert_eq!(
+        w,
+        AccountW {
+            name: Some("Alice".to_string()),
+            age: Some(30),
+            note: None,
+        }
+    );
+
+    let missing = AccountW {
+        name: Some("Alice".to_string()),
+        age: None,
+        note: None,
+    };
+    let result: Result<AccountUw, _> = missing.try_into();
+    assert_eq!(
+        result,
+        Err(UnwrappedError {
+            field_name: "age".into(),
+            source_field: "",
+            kind: UnwrappedErrorKind::Missing,
+        })
+    );
+}
+
+#[test]
+fn test_unwrapped_array_fields() {
+    #[derive(Debug, PartialEq, Unwrapped)]
+    struct Fixed {
+        id: Option<[u8; 16]>,
+    }
+
+    #[derive(Debug, PartialEq, Unwrapped)]
+    struct Buffer<const N: usize> {
+        bytes: Option<[u8; N]>,
+        len: usize,
+    }
+
+    let uw = FixedUw::try_from(Fixed { id: Some([7; 16]) }).unwrap();
+    assert_eq!(uw.id, [7; 16]);
+    assert_eq!(Fixed::from(uw), Fixed { id: Some([7; 16]) });
+
+    let uw = BufferUw::<4>::try_from(Buffer {
+        bytes: Some([1, 2, 3, 4]),
+        len: 4,
+    })
+    .unwrap();
+    assert_eq!(uw.bytes, [1, 2, 3, 4]);
+
+    let original: Buffer<4> = uw.into();
+    assert_eq!(original.bytes, Some([1, 2, 3, 4]));
+    assert!(
+        BufferUw::<2>::try_from(Buffer {
+            bytes: None,
+            len: 0
+        })
+        .is_err()
+    );
+}
+
+#[test]
+fn test_unwrapped_skip_drops_unused_lifetime() {
+    #[derive(Debug, PartialEq, Unwrapped)]
+    struct Request<'a, T: Clone + 'a> {
+        id: Option<T>,
+        #[unwrapped(skip)]
+        raw: &'a str,
+    }
+
+    // `RequestUw` no longer carries `'a`
+    let uw: RequestUw<u32> = RequestUw::try_from(Request {
+        id: Some(1),
+        raw: "GET /",
+    })
+    .unwrap();
+    assert_eq!(uw.id, 1);
+
+    let body = String::from("POST /");
+    let original = uw.into_original(&body);
+    assert_eq!(
+        original,
+        Request {
+            id: Some(1),
+            raw: "POST /",
+        }
+    );
+}
+
+mod as_string {
+    use std::{fmt::Display, str::FromStr};
+
+    use serde::{Deserialize as _, Deserializer, Serializer};
+
+    pub fn serialize<T: Display, S: Serializer>(
+        value: &T,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(value)
+    }
+
+    pub fn deserialize<'de, T, D>(deserializer: D) -> Result<T, D::Error>
+    where
+        T: FromStr,
+        T::Err: Display,
+        D: Deserializer<'de>,
+    {
+        String::deserialize(deserializer)?
+            .parse()
+            .map_err(serde::de::Error::custom)
+    }
+}
+
+#[test]
+fn test_unwrapped_forwards_serde_with() {
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Debug, PartialEq, Serialize, Unwrapped)]
+    #[unwrapped(derive(Debug, PartialEq, Serialize, Deserialize))]
+    struct Counter {
+        id: Option<u32>,
+        #[serde(rename = "total", with = "as_string")]
+        count: u64,
+    }
+
+    let uw = CounterUw::try_from(Counter {
+        id: Some(1),
+        count: 5,
+    })
+    .unwrap();
+
+    // Only `with` is forwarded, so the mirror keeps the field name
+    let json = serde_json::to_string(&uw).unwrap();
+    assert_eq!(json, r#"{"id":1,"count":"5"}"#);
+    assert_eq!(serde_json::from_str::<CounterUw>(&json).unwrap(), uw);
+}
+
+#[test]
+fn test_unwrapped_only_allowlist() {
+    #[derive(Debug, PartialEq, Unwrapped)]
+    #[unwrapped(only("name", "age", "email"))]
+    struct Wide {
+        name: Option<String>,
+        age: Option<u8>,
+        #[unwrapped(skip)]
+        email: Option<String>,
+        created_at: u64,
+        updated_at: u64,
+        revision: u32,
+    }
+
+    // Only the allowlisted fields are kept, and a field-level skip still applies
+    let uw = WideUw {
+        name: "Alice".to_string(),
+        age: 30,
+    };
+
+    let original = uw.into_original(None, 1, 2, 3);
+    assert_eq!(
+        original,
+        Wide {
+            name: Some("Alice".to_string()),
+            age: Some(30),
+            email: None,
+            created_at: 1,
+            updated_at: 2,
+            revision: 3,
+        }
+    );
+}
+
+#[test]
+fn test_unwrapped_analyze() {
+    #[derive(Debug, PartialEq, Unwrapped)]
+    #[unwrapped(analyze, attr(allow(dead_code)))]
+    struct Survey {
+        name: Option<String>,
+        email: Option<String>,
+        score: u32,
+        #[unwrapped(skip)]
+        id: u64,
+    }
+
+    let survey = Survey {
+        name: Some("Alice".to_string()),
+        email: None,
+        score: 7,
+        id: 1,
+    };
+
+    assert_eq!(
+        SurveyUw::analyze(&survey),
+        vec![
+            ("name", Presence::Present),
+            ("email", Presence::Missing),
+            ("score", Presence::NotOptional),
+        ]
+    );
+}
+
+#[test]
+fn test_unwrapped_analyze_when_tag() {
+    #[derive(Debug, PartialEq)]
+    enum Kind {
+        Cash,
+        Card,
+    }
+
+    #[derive(Debug, PartialEq, Unwrapped)]
+    #[unwrapped(tag = kind, analyze, attr(allow(dead_code)))]
+    struct Payment {
+        kind: Kind,
+        #[unwrapped(when_tag = Card)]
+        card: Option<String>,
+    }
+
+    // Required while the tag is `Card`, carried over as-is otherwise
+    let presence = |kind, card: Option<&str>| {
+        PaymentUw::analyze(&Payment {
+            kind,
+            card: card.map(str::to_string),
+        })
+    };
+    assert_eq!(
+        presence(Kind::Card, None),
+        vec![("kind", Presence::NotOptional), ("card", Presence::Missing)]
+    );
+    assert_eq!(
+        presence(Kind::Card, Some("4242")),
+        vec![("kind", Presence::NotOptional), ("card", Presence::Present)]
+    );
+    assert_eq!(
+        presence(Kind::Cash, None),
+        vec![
+            ("kind", Presence::NotOptional),
+            ("card", Presence::NotOptional)
+        ]
+    );
+}
+
+#[test]
+fn test_unwrapped_smart_default() {
+    #[derive(Debug, PartialEq)]
+    struct Port(u16);
+
+    #[derive(Debug, PartialEq, Unwrapped)]
+    #[unwrapped(smart_default, derive(Debug, PartialEq))]
+    struct Server<T> {
+        host: Option<String>,
+        tags: Option<Vec<T>>,
+        limits: Box<Option<(u8, [u16; 2])>>,
+        #[unwrapped(default = Port(8080))]
+        port: Option<Port>,
+        fallback: Option<Option<T>>,
+        #[unwrapped(skip)]
+        id: Port,
+    }
+
+    assert_eq!(
+        ServerUw::<Port>::default(),
+        ServerUw {
+            host: String::new(),
+            tags: Vec::new(),
+            limits: Box::new((0, [0, 0])),
+            port: Port(8080),
+            fallback: None,
+        }
+    );
+}
+
+#[test]
+fn test_unwrapped_missing_fields() {
+    #[derive(Debug, PartialEq, Unwrapped)]
+    #[unwrapped(derive(Debug), missing_fields)]
+    struct Survey {
+        #[unwrapped(rename = full_name)]
+        name: Option<String>,
+        email: Option<String>,
+        note: Box<Option<String>>,
+        score: u32,
+        #[unwrapped(skip)]
+        id: Option<u64>,
+    }
+
+    let survey = Survey {
+        name: None,
+        email: Some("alice@example.com".to_string()),
+        note: Box::new(None),
+        score: 7,
+        id: None,
+    };
+
+    // Names match the ones `try_from` reports, so renamed fields keep their original name
+    assert_eq!(SurveyUw::missing_fields(&survey), vec!["name", "note"]);
+    assert_eq!(SurveyUw::try_from(survey).unwrap_err().field_name, "name");
+
+    let complete = Survey {
+        name: Some("Alice".to_string()),
+        email: Some("alice@example.com".to_string()),
+        note: Box::new(Some("hi".to_string())),
+        score: 7,
+        id: None,
+    };
+    assert!(SurveyUw::missing_fields(&complete).is_empty());
+}
+
+#[test]
+fn test_unwrapped_missing_fields_when_tag() {
+    #[derive(Debug, PartialEq)]
+    enum Kind {
+        Cash,
+        Card,
+    }
+
+    #[derive(Debug, PartialEq, Unwrapped)]
+    #[unwrapped(derive(Debug), tag = kind, missing_fields, attr(allow(dead_code)))]
+    struct Payment {
+        kind: Kind,
+        #[unwrapped(when_tag = Card)]
+        card: Option<String>,
+        amount: Option<u32>,
+    }
+
+    // A `when_tag` field is reported only while its tag requires it
+    let card = Payment {
+        kind: Kind::Card,
+        card: None,
+        amount: None,
+    };
+    assert_eq!(PaymentUw::missing_fields(&card), vec!["card", "amount"]);
+    assert_eq!(PaymentUw::try_from(card).unwrap_err().field_name, "card");
+
+    let cash = Payment {
+        kind: Kind::Cash,
+        card: None,
+        amount: Some(5),
+    };
+    assert!(PaymentUw::missing_fields(&cash).is_empty());
+    assert!(PaymentUw::try_from(cash).is_ok());
+}
+
+#[test]
+fn test_unwrapped_auto_default() {
+    #[derive(Debug, PartialEq)]
+    struct Port(u16);
+
+    #[derive(Debug, PartialEq, Unwrapped)]
+    #[unwrapped(auto_default, derive(Debug, PartialEq))]
+    #[allow(dead_code)]
+    struct Server {
+        host: Option<String>,
+        #[unwrapped(default = Port(8080))]
+        port: Option<Port>,
+        #[unwrapped(default = "vec![\"admin\".to_string()]")]
+        roles: Vec<String>,
+        retries: u8,
+    }
+
+    assert_eq!(
+        ServerUw::default(),
+        ServerUw {
+            host: String::new(),
+            port: Port(8080),
+            roles: vec!["admin".to_string()],
+            retries: 0,
+        }
+    );
+}
+
+#[test]
+fn test_unwrapped_try_from_counted() {
+    #[derive(Debug, PartialEq)]
+    enum Kind {
+        Basic,
+        Premium,
+    }
+
+    #[derive(Debug, PartialEq, Unwrapped)]
+    #[unwrapped(counted, tag = kind, attr(allow(dead_code)))]
+    struct Plan {
+        kind: Kind,
+        name: Option<String>,
+        #[unwrapped(when_tag = Premium)]
+        discount: Option<u8>,
+        #[unwrapped(skip)]
+        internal: Option<u8>,
+    }
+
+    let (uw, present) = PlanUw::try_from_counted(Plan {
+        kind: Kind::Basic,
+        name: Some("Starter".to_string()),
+        discount: None,
+        internal: None,
+    })
+    .unwrap();
+    assert_eq!(uw.name, "Starter");
+    assert_eq!(present, 1);
+
+    let (_, present) = PlanUw::try_from_counted(Plan {
+        kind: Kind::Premium,
+        name: Some("Gold".to_string()),
+        discount: Some(10),
+        internal: Some(1),
+    })
+    .unwrap();
+    assert_eq!(present, 2);
+
+    let result = PlanUw::try_from_counted(Plan {
+        kind: Kind::Basic,
+        name: None,
+        discount: None,
+        internal: None,
+    });
+    assert_eq!(
+        result.err(),
+        Some(UnwrappedError {
+            field_name: "name".into(),
+            source_field: "",
+            kind: UnwrappedErrorKind::Missing,
+        })
+    );
+}
+
+#[test]
+fn test_unwrapped_forwards_serde_borrow() {
+    use std::borrow::Cow;
+
+    use serde::Deserialize;
+
+    #[derive(Debug, PartialEq, Deserialize, Unwrapped)]
+    #[unwrapped(derive(Debug, PartialEq, Deserialize))]
+    #[allow(dead_code)]
+    struct Message<'a> {
+        #[serde(borrow)]
+        title: Option<Cow<'a, str>>,
+        #[serde(borrow)]
+        body: Option<&'a str>,
+    }
+
+    let json = String::from(r#"{"title":"hi","body":"hello"}"#);
+    let uw: MessageUw = serde_json::from_str(&json).unwrap();
+
+    // Both fields borrow from the input instead of allocating
+    assert!(matches!(uw.title, Cow::Borrowed("hi")));
+    assert_eq!(uw.body, "hello");
+}
+
+#[test]
+fn test_unwrapped_from_into() {
+    #[derive(Debug, PartialEq, Unwrapped)]
+    #[unwrapped(from_into)]
+    struct Point {
+        x: Option<i32>,
+        y: Option<i32>,
+    }
+
+    impl From<(i32, i32)> for Point {
+        fn from((x, y): (i32, i32)) -> Self {
+            Self {
+                x: Some(x),
+                y: Some(y),
+            }
+        }
+    }
+
+    let uw = PointUw::from_into((1, 2)).unwrap();
+    assert_eq!((uw.x, uw.y), (1, 2));
+
+    // The original itself also converts into itself
+    let result = PointUw::from_into(Point {
+        x: None,
+        y: Some(2),
+    });
+    assert_eq!(
+        result.err(),
+        Some(UnwrappedError {
+            field_name: "x".into(),
+            source_field: "",
+            kind: UnwrappedErrorKind::Missing,
+        })
+    );
+}
+
+#[test]
+fn test_unwrapped_nested_mirror_fields() {
+    #[derive(Clone, Debug, PartialEq, Unwrapped)]
+    #[unwrapped(derive(Clone, Debug, PartialEq))]
+    struct Inner {
+        value: Option<u8>,
+    }
+
+    #[derive(Debug, PartialEq, Unwrapped)]
+    #[unwrapped(derive(Debug, PartialEq))]
+    struct Outer {
+        // Mirror types as plain fields are carried over untouched
+        direct: InnerUw,
+        projected: <Inner as Unwrapped>::Unwrapped,
+        // An Option of a mirror type only unwraps the outer Option
+        maybe: Option<InnerUw>,
+    }
+
+    let inner = InnerUw::try_from(Inner { value: Some(1) }).unwrap();
+    let outer = Outer {
+        direct: inner.clone(),
+        projected: inner.clone(),
+        maybe: Some(inner.clone()),
+    };
+
+    let uw = OuterUw::try_from(outer).unwrap();
+    assert_eq!(
+        uw,
+        OuterUw {
+            direct: inner.clone(),
+            projected: inner.clone(),
+            maybe: inner.clone(),
+        }
+    );
+
+    let back: Outer = uw.into();
+    assert_eq!(back.maybe, Some(inner));
+}
+
+#[test]
+fn test_unwrapped_struct_from_macro_rules() {
+    macro_rules! form {
+        ($name:ident, $field:ident) => {
+            #[derive(Debug, PartialEq, Unwrapped, Wrapped)]
+            #[unwrapped(derive(Debug, PartialEq))]
+            #[wrapped(derive(Debug, PartialEq))]
+            struct $name {
+                id: Option<u32>,
+                $field: Option<String>,
+                #[unwrapped(skip)]
+                #[wrapped(skip)]
+                revision: u32,
+            }
+        };
+    }
+
+    form!(Form, label);
+
+    // Field idents from both the macro body and its arguments resolve in the generated bodies
+    let uw = FormUw::try_from(Form {
+        id: Some(1),
+        label: Some("a".to_string()),
+        revision: 2,
+    })
+    .unwrap();
+    assert_eq!(
+        uw,
+        FormUw {
+            id: 1,
+            label: "a".to_string(),
+        }
+    );
+    assert_eq!(uw.into_original(2).revision, 2);
+
+    let w = FormW {
+        id: None,
+        label: None,
+    };
+    assert_eq!(
+        w.into_original(3).unwrap(),
+        Form {
+            id: None,
+            label: None,
+            revision: 3,
+        }
+    );
+}
+
+#[test]
+fn test_unwrapped_debug_checks() {
+    #[derive(Clone, Debug, PartialEq, Unwrapped)]
+    #[unwrapped(debug_checks, tag = kind)]
+    struct Order {
+        kind: OrderKind,
+        id: Option<u32>,
+        #[unwrapped(when_tag = Express)]
+        courier: Option<String>,
+        #[unwrapped(skip)]
+        note: String,
+    }
+
+    #[derive(Clone, Debug, PartialEq)]
+    enum OrderKind {
+        Standard,
+        Express,
+    }
+
+    let uw = OrderUw::try_from(Order {
+        kind: OrderKind::Express,
+        id: Some(1),
+        courier: Some("ACME".to_string()),
+        note: String::new(),
+    })
+    .unwrap();
+    assert_eq!(uw.id, 1);
+    assert_eq!(uw.courier.as_deref(), Some("ACME"));
+
+    let result = OrderUw::try_from(Order {
+        kind: OrderKind::Standard,
+        id: None,
+        courier: None,
+        note: String::new(),
+    });
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_unwrapped_metrics() {
+    use std::cell::RefCell;
+
+    thread_local! {
+        static RECORDED: RefCell<Vec<(&'static str, bool)>> = const { RefCell::new(Vec::new()) };
+    }
+
+    fn record(field: &'static str, present: bool) {
+        RECORDED.with(|recorded| recorded.borrow_mut().push((field, present)));
+    }
+
+    #[derive(Debug, PartialEq, Unwrapped)]
+    #[unwrapped(metrics = "record")]
+    struct Signup {
+        email: Option<String>,
+        #[unwrapped(rename = display_name)]
+        name: Option<String>,
+        note: Box<Option<String>>,
+        age: u32,
+        #[unwrapped(skip)]
+        referrer: Option<String>,
+    }
+
+    // Every unwrapped field is reported in declaration order, even after one is missing
+    let result = SignupUw::try_from(Signup {
+        email: None,
+        name: Some("Alice".to_string()),
+        note: Box::new(None),
+        age: 30,
+        referrer: None,
+    });
+    assert_eq!(result.err().unwrap().field_name, "email");
+    assert_eq!(
+        RECORDED.take(),
+        [("email", false), ("name", true), ("note", false)]
+    );
+
+    SignupUw::try_from(Signup {
+        email: Some("alice@example.com".to_string()),
+        name: Some("Alice".to_string()),
+        note: Box::new(Some("hi".to_string())),
+        age: 30,
+        referrer: None,
+    })
+    .unwrap();
+    assert_eq!(
+        RECORDED.take(),
+        [("email", true), ("name", true), ("note", true)]
+    );
+}
+
+#[test]
+fn test_unwrapped_method_name() {
+    #[derive(Debug, PartialEq, Unwrapped)]
+    #[unwrapped(method_name = "validate", expect)]
+    struct Signup {
+        email: Option<String>,
+    }
+
+    let uw = SignupUw::validate(Signup {
+        email: Some("a@example.com".to_string()),
+    })
+    .unwrap();
+    assert_eq!(uw.email, "a@example.com");
+
+    // Helpers built on the conversion use the renamed method
+    let uw = SignupUw::expect_unwrapped(Signup {
+        email: Some("b@example.com".to_string()),
+    });
+    assert_eq!(uw.email, "b@example.com");
+}
+
+#[test]
+fn test_unwrapped_reinterpret() {
+    #[derive(Debug, PartialEq, Unwrapped)]
+    #[unwrapped(reinterpret, derive(Debug, PartialEq))]
+    #[repr(C)]
+    struct Sample {
+        timestamp: u64,
+        value: f32,
+        label: &'static str,
+    }
+
+    let uw = SampleUw {
+        timestamp: 7,
+        value: 1.5,
+        label: "cpu",
+    };
+
+    assert_eq!(
+        uw.as_original(),
+        &Sample {
+            timestamp: 7,
+            value: 1.5,
+            label: "cpu",
+        }
+    );
+}
+
+#[test]
+fn test_unwrapped_presence_mask() {
+    #[derive(Debug, PartialEq, Unwrapped)]
+    #[unwrapped(presence_mask, attr(allow(dead_code)))]
+    struct Packet {
+        id: u32,
+        #[unwrapped(skip)]
+        checksum: Option<u32>,
+        source: Option<String>,
+        payload: Option<Vec<u8>>,
+    }
+
+    let mask = PacketUw::presence_mask(&Packet {
+        id: 1,
+        checksum: Some(0),
+        source: None,
+        payload: Some(vec![1]),
+    });
+
+    // Bits follow declaration order with `checksum` excluded: id, source, payload
+    assert_eq!(mask, 0b101);
+}
+
+#[test]
+fn test_unwrapped_presence_mask_boxed_option() {
+    #[derive(Debug, PartialEq, Unwrapped)]
+    #[unwrapped(presence_mask)]
+    struct Node {
+        value: u32,
+        next: Box<Option<u32>>,
+    }
+
+    let empty = Node {
+        value: 1,
+        next: Box::new(None),
+    };
+    let linked = Node {
+        value: 1,
+        next: Box::new(Some(2)),
+    };
+
+    assert_eq!(NodeUw::presence_mask(&empty), 0b01);
+    assert_eq!(NodeUw::presence_mask(&linked), 0b11);
+}
+
+#[test]
+fn test_unwrapped_forward_attrs_serde_verbatim() {
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize, Unwrapped)]
+    #[unwrapped(
+        derive(Debug, PartialEq, Serialize, Deserialize),
+        forward_attrs("serde")
+    )]
+    #[serde(rename_all = "UPPERCASE")]
+    struct Row {
+        #[serde(rename = "row_id")]
+        id: Option<u32>,
+        label: Option<String>,
+    }
+
+    let uw = RowUw::try_from(Row {
+        id: Some(1),
+        label: Some("a".to_string()),
+    })
+    .unwrap();
+    // Every serde key is copied, container and field alike
+    assert_eq!(
+        serde_json::to_string(&uw).unwrap(),
+        r#"{"row_id":1,"LABEL":"a"}"#
+    );
+}
+
+#[test]
+fn test_unwrapped_forwards_serde_skips() {
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize, Unwrapped)]
+    #[unwrapped(derive(Debug, PartialEq, Serialize, Deserialize))]
+    struct Session {
+        user: Option<String>,
+        #[serde(skip_serializing)]
+        token: Option<String>,
+        #[serde(skip_deserializing)]
+        hits: Option<u32>,
+    }
+
+    let uw = SessionUw::try_from(Session {
+        user: Some("ann".to_string()),
+        token: Some("secret".to_string()),
+        hits: Some(3),
+    })
+    .unwrap();
+
+    let json = serde_json::to_string(&uw).unwrap();
+    assert_eq!(json, r#"{"user":"ann","hits":3}"#);
+
+    // `hits` lost its `Option`, so deserializing fills it with `u32::default()`
+    let back: SessionUw = serde_json::from_str(r#"{"user":"ann","token":"t","hits":3}"#).unwrap();
+    assert_eq!(back.hits, 0);
+}
+
+#[test]
+fn test_unwrapped_internally_tagged_enum_field() {
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    #[serde(tag = "type", rename_all = "snake_case")]
+    enum Shape {
+        Circle { radius: f64 },
+        Square { side: f64 },
+    }
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize, Unwrapped)]
+    #[unwrapped(derive(Debug, PartialEq, Serialize, Deserialize))]
+    struct Drawing {
+        name: Option<String>,
+        #[serde(rename = "figure")]
+        shape: Option<Shape>,
+    }
+
+    let uw = DrawingUw::try_from(Drawing {
+        name: Some("logo".to_string()),
+        shape: Some(Shape::Circle { radius: 1.5 }),
+    })
+    .unwrap();
+
+    // The tag lives on `Shape` itself, so the mirror serializes it untouched
+    let json = serde_json::to_string(&uw).unwrap();
+    assert_eq!(
+        json,
+        r#"{"name":"logo","shape":{"type":"circle","radius":1.5}}"#
+    );
+    assert_eq!(serde_json::from_str::<DrawingUw>(&json).unwrap(), uw);
+
+    let original: Drawing =
+        serde_json::from_str(r#"{"name":"logo","figure":{"type":"square","side":2.0}}"#).unwrap();
+    let uw = DrawingUw::try_from(original).unwrap();
+    assert_eq!(uw.shape, Shape::Square { side: 2.0 });
+    assert_eq!(
+        serde_json::to_string(&uw).unwrap(),
+        r#"{"name":"logo","shape":{"type":"square","side":2.0}}"#
+    );
+}
+
+#[test]
+fn test_unwrapped_view_borrows_fields() {
+    #[derive(Debug, PartialEq, Unwrapped)]
+    #[unwrapped(view, attr(allow(dead_code)))]
+    struct Article {
+        title: Option<String>,
+        tags: Option<Vec<String>>,
+        views: Option<u64>,
+        #[unwrapped(skip)]
+        draft: Option<String>,
+        author: String,
+    }
+
+    let article = Article {
+        title: Some("Hello".to_string()),
+        tags: Some(vec!["rust".to_string()]),
+        views: Some(3),
+        draft: None,
+        author: "ann".to_string(),
+    };
+
+    let view: ArticleView = article.view().unwrap();
+    let title: &str = view.title;
+    let tags: &[String] = view.tags;
+    assert_eq!(title, "Hello");
+    assert_eq!(tags, ["rust"]);
+    assert_eq!(*view.views, 3);
+    assert_eq!(view.author, "ann");
+
+    // The view borrows, so the original is still usable
+    assert_eq!(article.title.as_deref(), Some("Hello"));
+
+    #[derive(Unwrapped)]
+    #[unwrapped(view)]
+    struct Pair<'a, T> {
+        left: Option<&'a str>,
+        right: Option<T>,
+    }
+
+    let pair = Pair {
+        left: Some("l"),
+        right: Some(2),
+    };
+    let pair_view = pair.view().unwrap();
+    assert_eq!((*pair_view.left, *pair_view.right), ("l", 2));
+
+    let missing_title = Article {
+        title: None,
+        ..article
+    };
+    assert!(matches!(
+        missing_title.view(),
+        Err(UnwrappedError { field_name, .. }) if field_name == "title"
+    ));
+}
+
+#[test]
+fn test_unwrapped_field_assert() {
+    #[derive(Debug, PartialEq, Unwrapped)]
+    #[unwrapped(derive(Debug, PartialEq))]
+    struct Listing {
+        #[unwrapped(assert = "!value.is_empty()")]
+        title: Option<String>,
+        #[unwrapped(assert = "value > 0")]
+        price: Option<u32>,
+        #[unwrapped(assert = value <= 5)]
+        rating: u8,
+    }
+
+    let make = || Listing {
+        title: Some("lamp".to_string()),
+        price: Some(20),
+        rating: 4,
+    };
+
+    assert_eq!(
+        ListingUw::try_from(make()).unwrap(),
+        ListingUw {
+            title: "lamp".to_string(),
+            price: 20,
+            rating: 4
+        }
+    );
+
+    // A failed assertion reports the field, with its own kind and message
+    let err = ListingUw::try_from(Listing {
+        price: Some(0),
+        ..make()
+    })
+    .unwrap_err();
+    assert_eq!(err.field_name, "price");
+    assert_eq!(err.kind, UnwrappedErrorKind::Assertion);
+    assert_eq!(err.to_string(), "Assertion failed for field 'price'");
+
+    let err = ListingUw::try_from(Listing {
+        price: None,
+        ..make()
+    })
+    .unwrap_err();
+    assert_eq!(err.kind, UnwrappedErrorKind::Missing);
+    assert_eq!(
+        err.to_string(),
+        "Failed to unwrap an Option for field 'price', found None"
+    );
+
+    let err = ListingUw::try_from(Listing {
+        rating: 9,
+        ..make()
+    })
+    .unwrap_err();
+    assert_eq!(err.field_name, "rating");
+
+    // Fields are checked in declaration order, presence first
+    let err = ListingUw::try_from(Listing {
+        title: Some(String::new()),
+        price: None,
+        ..make()
+    })
+    .unwrap_err();
+    assert_eq!(err.field_name, "title");
+    assert_eq!(err.kind, UnwrappedErrorKind::Assertion);
+}
+
+#[test]
+fn test_unwrapped_field_with() {
+    fn parse_port(value: Option<String>) -> Result<u16, std::num::ParseIntError> {
+        value.as_deref().unwrap_or("80").parse()
+    }
+
+    fn port_to_string(port: u16) -> Option<String> {
+        Some(port.to_string())
+    }
+
+    #[derive(Debug, PartialEq, Unwrapped)]
+    #[unwrapped(derive(Debug, PartialEq))]
+    struct Endpoint {
+        host: Option<String>,
+        #[unwrapped(with = parse_port, ty = u16, back = port_to_string)]
+        port: Option<String>,
+        #[unwrapped(with = u8::try_from, ty = u8)]
+        retries: i64,
+    }
+
+    let uw = EndpointUw::try_from(Endpoint {
+        host: Some("localhost".to_string()),
+        port: Some("8080".to_string()),
+        retries: 3,
+    })
+    .unwrap();
+    assert_eq!(
+        uw,
+        EndpointUw {
+            host: "localhost".to_string(),
+            port: 8080,
+            retries: 3,
+        }
+    );
+
+    // The function decides what `None` means
+    let uw = EndpointUw::try_from(Endpoint {
+        host: Some("localhost".to_string()),
+        port: None,
+        retries: 3,
+    })
+    .unwrap();
+    assert_eq!(uw.port, 80);
+
+    // Its error is reported for the field and carried as the source
+    let err = EndpointUw::try_from(Endpoint {
+        host: Some("localhost".to_string()),
+        port: Some("http".to_string()),
+        retries: 3,
+    })
+    .unwrap_err();
+    assert_eq!(err.field_name, "port");
+    assert_eq!(
+        err.to_string(),
+        "Failed to convert field 'port': invalid digit found in string"
+    );
+    let source = std::error::Error::source(&err).unwrap();
+    assert!(source.is::<std::num::ParseIntError>());
+
+    // `back` converts the field back, `From` does otherwise
+    assert_eq!(
+        Endpoint::from(uw),
+        Endpoint {
+            host: Some("localhost".to_string()),
+            port: Some("80".to_string()),
+            retries: 3,
+        }
+    );
+}
+
+#[test]
+fn test_unwrapped_test_only_attrs() {
+    use serde::Serialize;
+
+    // rustc resolves `cfg_attr` before running the derive, so test-only attributes for the
+    // generated struct go through the helper attribute. Integration tests build with `cfg(test)`.
+    #[derive(Unwrapped)]
+    #[cfg_attr(test, unwrapped(derive(Debug, PartialEq, Serialize)))]
+    struct Reading {
+        #[cfg_attr(test, unwrapped(attr(serde(rename = "v"))))]
+        value: Option<i32>,
+    }
+
+    let uw = ReadingUw::try_from(Reading { value: Some(1) }).unwrap();
+    assert_eq!(uw, ReadingUw { value: 1 });
+    assert_eq!(serde_json::to_string(&uw).unwrap(), r#"{"v":1}"#);
+}
+
+#[test]
+fn test_unwrapped_builder_state_mod() {
+    // Both structs get a bon builder, and the mirror's state module is renamed
+    #[derive(bon::Builder, Debug, PartialEq, Unwrapped)]
+    #[unwrapped(
+        derive(bon::Builder, Debug, PartialEq),
+        builder_state_mod = "job_uw_state"
+    )]
+    struct Job {
+        name: Option<String>,
+        retries: u8,
+    }
+
+    let builder: JobUwBuilder<job_uw_state::Empty> = JobUw::builder();
+    let uw = builder.name("backup".to_string()).retries(3).build();
+    let job = Job::builder().name("backup".to_string()).retries(3).build();
+
+    assert_eq!(JobUw::try_from(job).unwrap(), uw);
+}
+
+#[test]
+fn test_unwrapped_provenance() {
+    #[derive(Debug, PartialEq, Unwrapped)]
+    #[unwrapped(derive(Debug, PartialEq), provenance, view)]
+    struct Shipment {
+        carrier: Option<String>,
+    }
+
+    let expected = |label| if cfg!(debug_assertions) { label } else { "" };
+
+    let err = ShipmentUw::try_from(Shipment { carrier: None }).unwrap_err();
+    assert_eq!(err.field_name, "carrier");
+    assert_eq!(err.source_field, expected("ShipmentUw::try_from"));
+
+    let err = Shipment { carrier: None }.view().err().unwrap();
+    assert_eq!(err.source_field, expected("ShipmentUw::view"));
+
+    // Without `provenance` the breadcrumb stays empty
+    #[derive(Debug, Unwrapped)]
+    struct Parcel {
+        carrier: Option<String>,
+    }
+    let err = ParcelUw::try_from(Parcel { carrier: None }).err().unwrap();
+    assert_eq!(err.source_field, "");
+}
+
+#[test]
+fn test_unwrapped_debug_map() {
+    use std::collections::HashMap;
+
+    #[derive(Unwrapped)]
+    #[unwrapped(debug_map)]
+    #[allow(dead_code)]
+    struct Request {
+        path: Option<String>,
+        status: u16,
+        #[unwrapped(skip)]
+        body: Vec<u8>,
+    }
+
+    let uw = RequestUw {
+        path: "/health".to_string(),
+        status: 200,
+    };
+
+    assert_eq!(
+        uw.to_debug_map(),
+        HashMap::from([
+            ("path".to_string(), r#""/health""#.to_string()),
+            ("status".to_string(), "200".to_string()),
+        ])
+    );
+}
+
+#[test]
+fn test_unwrapped_lone_lifetime() {
+    #[derive(Debug, PartialEq, Unwrapped)]
+    #[unwrapped(derive(Debug, PartialEq))]
+    struct Ref<'a> {
+        s: Option<&'a str>,
+    }
+
+    let text = String::from("borrowed");
+    let uw: RefUw<'_> = RefUw::try_from(Ref { s: Some(&text) }).unwrap();
+    assert_eq!(uw, RefUw { s: "borrowed" });
+
+    // The trait impl resolves to the generated struct with the same lifetime
+    let via_trait: <Ref<'_> as Unwrapped>::Unwrapped = uw;
+    let original: Ref<'_> = via_trait.into();
+    assert_eq!(
+        original,
+        Ref {
+            s: Some("borrowed")
+        }
+    );
+
+    // Skip path: `into_original` carries the lifetime as well
+    #[derive(Debug, PartialEq, Unwrapped)]
+    struct RefSkip<'a> {
+        s: Option<&'a str>,
+        #[unwrapped(skip)]
+        len: usize,
+    }
+
+    let uw = RefSkipUw::try_from(RefSkip {
+        s: Some(&text),
+        len: 8,
+    })
+    .unwrap();
+    assert_eq!(
+        uw.into_original(8),
+        RefSkip {
+            s: Some("borrowed"),
+            len: 8
+        }
+    );
+}
+
+#[test]
+fn test_unwrapped_trait_constants() {
+    #[derive(Unwrapped, Wrapped)]
+    #[unwrapped(attr(allow(dead_code)))]
+    #[wrapped(attr(allow(dead_code)))]
+    #[allow(dead_code)]
+    struct Metric {
+        name: Option<String>,
+        value: f64,
+        #[unwrapped(skip)]
+        unit: String,
+    }
+
+    fn describe<T: Unwrapped + Wrapped>() -> (&'static str, usize, usize) {
+        (
+            <T as Unwrapped>::SOURCE,
+            <T as Unwrapped>::FIELD_COUNT,
+            <T as Wrapped>::FIELD_COUNT,
+        )
+    }
+
+    // Skipped fields are not counted
+    assert_eq!(describe::<Metric>(), ("Metric", 2, 3));
+
+    // Manual impls keep compiling thanks to the defaults
+    struct Manual;
+    impl Unwrapped for Manual {
+        type Unwrapped = ();
+    }
+    assert_eq!((Manual::SOURCE, Manual::FIELD_COUNT), ("", 0));
+}
+
+#[test]
+fn test_unwrapped_unsized_behind_pointer() {
+    use std::{rc::Rc, sync::Arc};
+
+    #[derive(Debug, PartialEq, Unwrapped)]
+    #[unwrapped(derive(Debug, PartialEq), view, auto_default)]
+    struct Interned {
+        name: Option<Box<str>>,
+        bytes: Option<Arc<[u8]>>,
+        tags: Option<Rc<[Box<str>]>>,
+    }
+
+    let original = Interned {
+        name: Some("id".into()),
+        bytes: Some(Arc::from([1u8, 2].as_slice())),
+        tags: Some(Rc::from(vec![Box::from("a")])),
+    };
+
+    let view = original.view().unwrap();
+    assert_eq!(&**view.name, "id");
+
+    let uw = InternedUw::try_from(original).unwrap();
+    assert_eq!(&*uw.name, "id");
+    assert_eq!(&*uw.bytes, [1, 2]);
+    assert_eq!(uw.tags.len(), 1);
+
+    let back: Interned = uw.into();
+    assert_eq!(back.name.as_deref(), Some("id"));
+
+    assert_eq!(&*InternedUw::default().name, "");
+}
+
+#[test]
+fn test_unwrapped_into_original_or() {
+    #[derive(Debug, PartialEq, Unwrapped)]
+    #[unwrapped(into_original_or)]
+    struct Settings {
+        theme: Option<String>,
+        #[unwrapped(skip)]
+        revision: u64,
+        #[unwrapped(skip)]
+        owner: String,
+    }
+
+    let base = Settings {
+        theme: Some("light".to_string()),
+        revision: 7,
+        owner: "ann".to_string(),
+    };
+    let uw = SettingsUw {
+        theme: "dark".to_string(),
+    };
+
+    // `theme` comes from the mirror, skipped fields from the fallback
+    assert_eq!(
+        uw.into_original_or(&base),
+        Settings {
+            theme: Some("dark".to_string()),
+            revision: 7,
+            owner: "ann".to_string(),
+        }
+    );
+}
+
+#[test]
+fn test_unwrapped_try_from_into() {
+    #[derive(Debug, PartialEq)]
+    enum AppError {
+        Missing(String),
+    }
+
+    impl From<UnwrappedError> for AppError {
+        fn from(err: UnwrappedError) -> Self {
+            AppError::Missing(err.field_name.into_owned())
+        }
+    }
+
+    #[derive(Debug, PartialEq, Unwrapped)]
+    #[unwrapped(derive(Debug, PartialEq))]
+    struct Upload {
+        path: Option<String>,
+    }
+
+    // The return type picks `E`, no `map_err` needed
+    fn load(upload: Upload) -> Result<UploadUw, AppError> {
+        UploadUw::try_from_into(upload)
+    }
+
+    assert_eq!(
+        load(Upload {
+            path: Some("a.txt".to_string())
+        }),
+        Ok(UploadUw {
+            path: "a.txt".to_string()
+        })
+    );
+    assert_eq!(
+        load(Upload { path: None }),
+        Err(AppError::Missing("path".to_string()))
+    );
+}
+
+#[test]
+fn test_unwrapped_enum_variants() {
+    #[derive(Debug, Clone, PartialEq, Unwrapped)]
+    #[unwrapped(derive(Debug, Clone, PartialEq))]
+    enum Message {
+        Join { user: Option<String>, room: u32 },
+        Move(Option<i32>, Option<i32>),
+        Leave,
+    }
+
+    let uw = MessageUw::try_from(Message::Join {
+        user: Some("ann".to_string()),
+        room: 4,
+    })
+    .unwrap();
+    assert_eq!(
+        uw,
+        MessageUw::Join {
+            user: "ann".to_string(),
+            room: 4
+        }
+    );
+    assert_eq!(
+        Message::from(uw),
+        Message::Join {
+            user: Some("ann".to_string()),
+            room: 4
+        }
+    );
+
+    let uw = MessageUw::try_from(Message::Move(Some(1), Some(-2))).unwrap();
+    assert_eq!(uw, MessageUw::Move(1, -2));
+    assert_eq!(Message::from(uw), Message::Move(Some(1), Some(-2)));
+    assert_eq!(
+        MessageUw::try_from(Message::Leave).unwrap(),
+        MessageUw::Leave
+    );
+
+    let err = MessageUw::try_from(Message::Join {
+        user: None,
+        room: 1,
+    })
+    .unwrap_err();
+    assert_eq!(err.field_name, "Join.user");
+    let err = MessageUw::try_from(Message::Move(Some(1), None)).unwrap_err();
+    assert_eq!(err.field_name, "Move.1");
+}
+
+#[test]
+fn test_unwrapped_enum_forwards_serde_rename_all_fields() {
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Unwrapped)]
+    #[unwrapped(derive(Debug, Clone, PartialEq, Serialize, Deserialize))]
+    #[serde(rename_all_fields = "camelCase")]
+    enum Event {
+        Signup {
+            user_name: Option<String>,
+            referral_code: u32,
+        },
+        Logout,
+    }
+
+    let original = Event::Signup {
+        user_name: Some("ann".to_string()),
+        referral_code: 7,
+    };
+    let uw = EventUw::try_from(original.clone()).unwrap();
+
+    let json = serde_json::to_string(&uw).unwrap();
+    assert_eq!(json, r#"{"Signup":{"userName":"ann","referralCode":7}}"#);
+    assert_eq!(serde_json::to_string(&original).unwrap(), json);
+    assert_eq!(serde_json::from_str::<EventUw>(&json).unwrap(), uw);
+    assert_eq!(
+        serde_json::to_string(&EventUw::Logout).unwrap(),
+        serde_json::to_string(&Event::Logout).unwrap()
+    );
+}
+
+#[test]
+fn test_unwrapped_try_from_trait() {
+    #[derive(Debug, PartialEq, Unwrapped)]
+    #[unwrapped(derive(Debug, PartialEq))]
+    struct Order {
+        id: Option<u32>,
+        note: String,
+    }
+
+    fn load(order: Order) -> Result<OrderUw, UnwrappedError> {
+        let uw: OrderUw = order.try_into()?;
+        Ok(uw)
+    }
+
+    assert_eq!(
+        load(Order {
+            id: Some(3),
+            note: "rush".to_string()
+        })
+        .unwrap(),
+        OrderUw {
+            id: 3,
+            note: "rush".to_string()
+        }
+    );
+    let err = load(Order {
+        id: None,
+        note: String::new(),
+    })
+    .unwrap_err();
+    assert_eq!(err.field_name, "id");
+    assert_eq!(
+        <OrderUw as TryFrom<Order>>::try_from(Order {
+            id: None,
+            note: String::new()
+        }),
+        Err(err)
+    );
+}
+
+#[test]
+fn test_unwrapped_try_from_boxed() {
+    #[derive(Debug, PartialEq, Unwrapped)]
+    #[unwrapped(derive(Debug, PartialEq), boxed_ctor)]
+    struct Report {
+        title: Option<String>,
+        rows: [u64; 64],
+    }
+
+    let uw = ReportUw::try_from_boxed(Box::new(Report {
+        title: Some("q3".to_string()),
+        rows: [1; 64],
+    }))
+    .unwrap();
+    assert_eq!(uw.title, "q3");
+    assert_eq!(uw.rows, [1; 64]);
+
+    let err = ReportUw::try_from_boxed(Box::new(Report {
+        title: None,
+        rows: [0; 64],
+    }))
+    .unwrap_err();
+    assert_eq!(err.field_name, "title");
+}
+
+#[test]
+fn test_unwrapped_recursive_fields() {
+    #[derive(Debug, Clone, PartialEq, Unwrapped)]
+    #[unwrapped(derive(Debug, Clone, PartialEq))]
+    pub struct Port {
+        number: Option<u16>,
+    }
+
+    #[derive(Debug, Clone, PartialEq, Unwrapped)]
+    #[unwrapped(derive(Debug, Clone, PartialEq), recursive)]
+    pub struct Server {
+        #[unwrapped(recursive = false)]
+        host: Option<String>,
+        port: Option<Port>,
+    }
+
+    #[derive(Debug, Clone, PartialEq, Unwrapped)]
+    #[unwrapped(derive(Debug, Clone, PartialEq))]
+    struct Config {
+        #[unwrapped(recursive)]
+        server: Option<Server>,
+        name: Option<String>,
+    }
+
+    let config = Config {
+        server: Some(Server {
+            host: Some("localhost".to_string()),
+            port: Some(Port { number: Some(80) }),
+        }),
+        name: Some("dev".to_string()),
+    };
+    let uw = ConfigUw::try_from(config.clone()).unwrap();
+    assert_eq!(
+        uw,
+        ConfigUw {
+            server: ServerUw {
+                host: "localhost".to_string(),
+                port: PortUw { number: 80 },
+            },
+            name: "dev".to_string(),
+        }
+    );
+    assert_eq!(Config::from(uw), config);
+
+    let err = ConfigUw::try_from(Config {
+        server: None,
+        name: None,
+    })
+    .unwrap_err();
+    assert_eq!(err.field_name, "server");
+
+    // The innermost `None` surfaces through both levels, named by its path
+    let err = ConfigUw::try_from(Config {
+        server: Some(Server {
+            host: Some("localhost".to_string()),
+            port: Some(Port { number: None }),
+        }),
+        name: Some("dev".to_string()),
+    })
+    .unwrap_err();
+    assert_eq!(err.field_name, "server.port.number");
+}
+
+#[test]
+fn test_unwrapped_try_from_partial() {
+    #[derive(Debug, Clone, PartialEq, Unwrapped)]
+    #[unwrapped(derive(Debug, PartialEq), collect_errors)]
+    struct SignupForm {
+        email: Option<String>,
+        password: Option<String>,
+        newsletter: bool,
+    }
+
+    let (uw, errors) = SignupFormUw::try_from_partial(SignupForm {
+        email: None,
+        password: None,
+        newsletter: true,
+    });
+    assert_eq!(uw, None);
+    let names: Vec<&str> = errors.iter().map(|err| &*err.field_name).collect();
+    assert_eq!(names, ["email", "password"]);
+
+    let (uw, errors) = SignupFormUw::try_from_partial(SignupForm {
+        email: Some("a@b.c".to_string()),
+        password: Some("hunter2".to_string()),
+        newsletter: false,
+    });
+    assert!(errors.is_empty());
+    assert_eq!(
+        uw,
+        Some(SignupFormUw {
+            email: "a@b.c".to_string(),
+            password: "hunter2".to_string(),
+            newsletter: false,
+        })
+    );
+}
+
+#[test]
+fn test_unwrapped_boxed_optional_fields() {
+    #[derive(Debug, Clone, PartialEq)]
+    struct Payload {
+        bytes: Vec<u8>,
+    }
+
+    #[derive(Debug, Clone, PartialEq, Unwrapped)]
+    #[unwrapped(derive(Debug, PartialEq))]
+    struct Envelope {
+        outer: Option<Box<Payload>>,
+        inner: Box<Option<Payload>>,
+    }
+
+    let envelope = Envelope {
+        outer: Some(Box::new(Payload { bytes: vec![1] })),
+        inner: Box::new(Some(Payload { bytes: vec![2] })),
+    };
+    let uw = EnvelopeUw::try_from(envelope.clone()).unwrap();
+    // Both orderings unwrap to `Box<Payload>`
+    assert_eq!(
+        uw,
+        EnvelopeUw {
+            outer: Box::new(Payload { bytes: vec![1] }),
+            inner: Box::new(Payload { bytes: vec![2] }),
+        }
+    );
+    assert_eq!(Envelope::from(uw), envelope);
+
+    let err = EnvelopeUw::try_from(Envelope {
+        outer: Some(Box::new(Payload { bytes: vec![] })),
+        inner: Box::new(None),
+    })
+    .unwrap_err();
+    assert_eq!(err.field_name, "inner");
+    let errors = EnvelopeUw::try_from_all(Envelope {
+        outer: None,
+        inner: Box::new(None),
+    })
+    .unwrap_err();
+    assert_eq!(errors.0.len(), 2);
+}
+
+#[test]
+fn test_unwrapped_builder() {
+    #[derive(Debug, PartialEq, Unwrapped)]
+    #[unwrapped(derive(Debug, PartialEq), builder)]
+    struct Listing<T> {
+        title: Option<String>,
+        price: Option<T>,
+        tags: Vec<String>,
+        #[unwrapped(skip)]
+        views: u64,
+    }
+
+    let listing = ListingUw::builder()
+        .title("Lamp")
+        .price(12u8)
+        .tags(vec!["home".to_string()])
+        .build();
+    assert_eq!(
+        listing,
+        ListingUw {
+            title: "Lamp".to_string(),
+            price: 12u8,
+            tags: vec!["home".to_string()],
+        }
+    );
+
+    // `build` names the first field that was never set
+    let result = std::panic::catch_unwind(|| ListingUw::<u8>::builder().title("Lamp").build());
+    let payload = result.err().unwrap();
+    let message = payload.downcast_ref::<String>().unwrap();
+    assert_eq!(message, "`price` must be set before calling `build`");
+}
+
+#[test]
+fn test_unwrapped_rename_field() {
+    #[derive(Debug, Clone, PartialEq, Unwrapped)]
+    #[unwrapped(derive(Debug, Clone, PartialEq))]
+    struct ApiUser {
+        #[unwrapped(rename = "id")]
+        user_id: Option<u64>,
+        #[unwrapped(rename = "display_name")]
+        name: String,
+    }
+
+    let original = ApiUser {
+        user_id: Some(7),
+        name: "Ann".to_string(),
+    };
+    let uw = ApiUserUw::try_from(original.clone()).unwrap();
+    assert_eq!(
+        uw,
+        ApiUserUw {
+            id: 7,
+            display_name: "Ann".to_string(),
+        }
+    );
+    assert_eq!(ApiUser::from(uw), original);
+
+    // Errors name the field of the original struct
+    let err = ApiUserUw::try_from(ApiUser {
+        user_id: None,
+        name: "Ann".to_string(),
+    })
+    .unwrap_err();
+    assert_eq!(err.field_name, "user_id");
+}
+
+#[test]
+fn test_unwrapped_shared_state_fields() {
+    use std::sync::{Arc, Mutex};
+
+    // Neither `Clone` nor `Default`, so no generated code may require them of `T`
+    #[derive(Debug)]
+    struct Connection {
+        sent: Vec<u8>,
+    }
+
+    #[derive(Unwrapped)]
+    #[unwrapped(derive(Debug), expect)]
+    struct Service<T> {
+        buffer: Option<Arc<Mutex<Vec<u8>>>>,
+        connection: Option<Arc<Mutex<T>>>,
+    }
+
+    let buffer = Arc::new(Mutex::new(vec![1, 2]));
+    let connection = Arc::new(Mutex::new(Connection { sent: Vec::new() }));
+    let uw = ServiceUw::try_from(Service {
+        buffer: Some(Arc::clone(&buffer)),
+        connection: Some(Arc::clone(&connection)),
+    })
+    .unwrap();
+
+    // Unwrapping moves the `Arc` out, so every handle shares the same state
+    assert!(Arc::ptr_eq(&uw.buffer, &buffer));
+    uw.buffer.lock().unwrap().push(3);
+    Arc::clone(&uw.connection).lock().unwrap().sent.push(9);
+    assert_eq!(*buffer.lock().unwrap(), [1, 2, 3]);
+    assert_eq!(connection.lock().unwrap().sent, [9]);
+
+    let original = Service::from(uw);
+    assert!(Arc::ptr_eq(
+        original.connection.as_ref().unwrap(),
+        &connection
+    ));
+
+    let errors = ServiceUw::<Connection>::try_from_all(Service {
+        buffer: None,
+        connection: None,
+    })
+    .err()
+    .unwrap();
+    assert_eq!(errors.0.len(), 2);
+}
+
+#[test]
+fn test_unwrapped_cfg_fields() {
+    // `cfg(not(test))` is never set here and `cfg(test)` always is, standing in for a feature
+    // turned off and on
+    #[derive(Debug, Clone, PartialEq, Unwrapped)]
+    #[unwrapped(
+        derive(Debug, Clone, PartialEq),
+        analyze,
+        builder,
+        counted,
+        debug_map,
+        presence_mask,
+        view
+    )]
+    struct Settings {
+        name: Option<String>,
+        #[cfg(not(test))]
+        extra: Option<u32>,
+        #[cfg(test)]
+        level: Option<u8>,
+        #[cfg(not(test))]
+        #[unwrapped(skip)]
+        secret: String,
+    }
+
+    let uw = SettingsUw::try_from(Settings {
+        name: Some("dev".to_string()),
+        level: Some(2),
+    })
+    .unwrap();
+    assert_eq!(
+        uw,
+        SettingsUw {
+            name: "dev".to_string(),
+            level: 2,
+        }
+    );
+    assert_eq!(
+        Settings::from(uw.clone()),
+        Settings {
+            name: Some("dev".to_string()),
+            level: Some(2),
+        }
+    );
+    assert_eq!(SettingsUw::builder().name("dev").level(2).build(), uw);
+    assert_eq!(uw.to_debug_map().len(), 2);
+    assert_eq!(<Settings as Unwrapped>::FIELD_COUNT, 2);
+    assert_eq!(
+        SettingsUw::analyze(&Settings {
+            name: None,
+            level: Some(2),
+        })
+        .len(),
+        2
+    );
+    let partial = Settings {
+        name: None,
+        level: Some(2),
+    };
+    assert_eq!(SettingsUw::presence_mask(&partial), 0b10);
+    assert!(partial.view().is_err());
+    assert_eq!(
+        SettingsUw::try_from_counted(Settings {
+            name: Some("dev".to_string()),
+            level: Some(2),
+        })
+        .unwrap()
+        .1,
+        2
+    );
+}
+
+mod visibility {
+    use unwrapped::{Unwrapped, Wrapped};
+
+    #[derive(Debug, Unwrapped, Wrapped)]
+    pub struct Account {
+        pub name: Option<String>,
+        password: Option<String>,
+    }
+
+    #[derive(Debug, Unwrapped, Wrapped)]
+    #[unwrapped(all_pub, name = AccountPubUw)]
+    #[wrapped(all_pub, name = AccountPubW)]
+    pub struct AccountPub {
+        pub name: Option<String>,
+        password: Option<String>,
+    }
+
+    pub fn account(name: &str, password: &str) -> Account {
+        Account {
+            name: Some(name.to_string()),
+            password: Some(password.to_string()),
+        }
+    }
+}
+
+#[test]
+fn test_field_visibility_mirrors_input() {
+    use visibility::{AccountPubUw, AccountPubW, AccountUw};
+
+    // Private fields stay private, public ones stay reachable
+    let account = AccountUw::try_from(visibility::account("ada", "hunter2")).unwrap();
+    assert_eq!(account.name, "ada");
+
+    // `all_pub` exposes every generated field
+    let open = AccountPubUw {
+        name: "ada".to_string(),
+        password: "hunter2".to_string(),
+    };
+    assert_eq!(open.password, "hunter2");
+
+    let patch = AccountPubW {
+        name: None,
+        password: Some("swordfish".to_string()),
+    };
+    assert_eq!(patch.password.as_deref(), Some("swordfish"));
+}
+
+#[test]
+fn test_unwrapped_tuple_struct() {
+    #[derive(Debug, PartialEq, Unwrapped)]
+    #[unwrapped(derive(Debug, PartialEq))]
+    struct Wrapper(Option<i32>, #[unwrapped(skip)] u8, String);
+
+    let uw = WrapperUw::try_from(Wrapper(Some(7), 3, "x".to_string())).unwrap();
+    // The skipped field is removed, so later fields move up a position
+    assert_eq!(uw, WrapperUw(7, "x".to_string()));
+    assert_eq!(uw.into_original(3), Wrapper(Some(7), 3, "x".to_string()));
+
+    let err = WrapperUw::try_from(Wrapper(None, 3, "x".to_string())).unwrap_err();
+    assert_eq!(err.field_name, "0");
+
+    #[derive(Debug, PartialEq, Unwrapped)]
+    #[unwrapped(derive(Debug, PartialEq))]
+    struct Point(Option<i32>, i32);
+
+    let uw = PointUw::try_from(Point(Some(1), 2)).unwrap();
+    assert_eq!(uw, PointUw(1, 2));
+    assert_eq!(Point::from(uw), Point(Some(1), 2));
+}
+
+#[test]
+fn test_unwrapped_try_from_all_collects_missing_fields() {
+    #[derive(Unwrapped)]
+    #[unwrapped(derive(Debug, PartialEq))]
+    struct Signup {
+        name: Option<String>,
+        email: Option<String>,
+        age: Option<u8>,
+        referrer: String,
+    }
+
+    let errors = SignupUw::try_from_all(Signup {
+        name: None,
+        email: Some("ann@example.com".to_string()),
+        age: None,
+        referrer: "ad".to_string(),
+    })
+    .unwrap_err();
+    let names: Vec<_> = errors.0.iter().map(|err| &*err.field_name).collect();
+    assert_eq!(names, ["name", "age"]);
+    assert_eq!(
+        errors.to_string(),
+        "Failed to unwrap Options for fields 'name' and 'age', found None"
+    );
+
+    let errors = SignupUw::try_from_all(Signup {
+        name: None,
+        email: None,
+        age: None,
+        referrer: "ad".to_string(),
+    })
+    .unwrap_err();
+    assert_eq!(
+        errors.to_string(),
+        "Failed to unwrap Options for fields 'name', 'email' and 'age', found None"
+    );
+
+    let single = UnwrappedErrors(vec![UnwrappedError {
+        field_name: "age".into(),
+        source_field: "",
+        kind: UnwrappedErrorKind::Missing,
+    }]);
+    assert_eq!(
+        single.to_string(),
+        "Failed to unwrap an Option for field 'age', found None"
+    );
+
+    let uw = SignupUw::try_from_all(Signup {
+        name: Some("ann".to_string()),
+        email: Some("ann@example.com".to_string()),
+        age: Some(30),
+        referrer: "ad".to_string(),
+    })
+    .unwrap();
+    assert_eq!(uw.age, 30);
+}
+
+#[test]
+fn test_unwrapped_try_from_all_when_tag() {
+    #[derive(Debug, PartialEq)]
+    enum Kind {
+        Cash,
+        Card,
+    }
+
+    #[derive(Unwrapped)]
+    #[unwrapped(derive(Debug), tag = kind, attr(allow(dead_code)))]
+    struct Payment {
+        kind: Kind,
+        #[unwrapped(when_tag = Card)]
+        card: Option<String>,
+        amount: Option<u32>,
+    }
+
+    // A `when_tag` field is collected with the others while its tag requires it
+    let errors = PaymentUw::try_from_all(Payment {
+        kind: Kind::Card,
+        card: None,
+        amount: None,
+    })
+    .unwrap_err();
+    let names: Vec<_> = errors.0.iter().map(|err| &*err.field_name).collect();
+    assert_eq!(names, ["card", "amount"]);
+
+    let errors = PaymentUw::try_from_all(Payment {
+        kind: Kind::Cash,
+        card: None,
+        amount: None,
+    })
+    .unwrap_err();
+    let names: Vec<_> = errors.0.iter().map(|err| &*err.field_name).collect();
+    assert_eq!(names, ["amount"]);
+}
+
+#[test]
+fn test_unwrapped_default_from_fills_none() {
+    #[derive(Debug, PartialEq, Unwrapped)]
+    #[unwrapped(default_from, skip_default, derive(Debug, PartialEq))]
+    struct Settings {
+        #[unwrapped(default = 42)]
+        val1: Option<i32>,
+        val2: Option<String>,
+        #[unwrapped(default = 1)]
+        retries: Option<u8>,
+        #[unwrapped(skip, default = 7)]
+        revision: u32,
+    }
+
+    let uw = SettingsUw::from(Settings {
+        val1: None,
+        val2: None,
+        retries: Some(3),
+        revision: 1,
+    });
+    assert_eq!(
+        uw,
+        SettingsUw {
+            val1: 42,
+            val2: String::new(),
+            retries: 3,
+        }
+    );
+
+    // `try_from` still fails on `None`
+    assert!(
+        SettingsUw::try_from(Settings {
+            val1: None,
+            val2: None,
+            retries: None,
+            revision: 1,
+        })
+        .is_err()
+    );
+
+    // `skip_default` uses the skipped field's `default`
+    assert_eq!(Settings::from(uw).revision, 7);
+}
+
+#[test]
+fn test_unwrapped_default_from_recursive_field() {
+    #[derive(Debug, PartialEq, Unwrapped)]
+    #[unwrapped(default_from, derive(Debug, PartialEq))]
+    struct Limits {
+        #[unwrapped(default = 10)]
+        max: Option<u32>,
+    }
+
+    #[derive(Debug, PartialEq, Unwrapped)]
+    #[unwrapped(derive(Debug, PartialEq))]
+    struct Service {
+        #[unwrapped(recursive)]
+        limits: Option<Limits>,
+    }
+
+    // `try_into` goes through the infallible `From`, so the nested `None` gets its default
+    #[allow(clippy::unnecessary_fallible_conversions)]
+    let limits: LimitsUw = Limits { max: None }.try_into().unwrap();
+    assert_eq!(limits, LimitsUw { max: 10 });
+
+    let uw = ServiceUw::try_from(Service {
+        limits: Some(Limits { max: None }),
+    })
+    .unwrap();
+    assert_eq!(uw.limits, LimitsUw { max: 10 });
+
+    let err = ServiceUw::try_from(Service { limits: None }).unwrap_err();
+    assert_eq!(err.field_name, "limits");
+}
+
+#[test]
+fn test_unwrapped_default_fn() {
+    fn default_tags() -> Vec<String> {
+        vec!["new".to_string()]
+    }
+
+    #[derive(Debug, PartialEq, Unwrapped)]
+    #[unwrapped(default_from, skip_default, derive(Debug, PartialEq))]
+    struct Post {
+        #[unwrapped(default_fn = "default_tags")]
+        tags: Option<Vec<String>>,
+        #[unwrapped(skip, default_fn = "String::new")]
+        draft: String,
+    }
+
+    let uw = PostUw::from(Post {
+        tags: None,
+        draft: "wip".to_string(),
+    });
+    assert_eq!(uw.tags, vec!["new".to_string()]);
+    assert_eq!(
+        Post::from(uw),
+        Post {
+            tags: Some(vec!["new".to_string()]),
+            draft: String::new(),
+        }
+    );
+}
+
+#[test]
+fn test_unwrapped_default_from_serde_default() {
+    fn default_tags() -> Option<Vec<String>> {
+        Some(vec!["new".to_string()])
+    }
+
+    fn no_title() -> Option<String> {
+        None
+    }
+
+    #[derive(Debug, PartialEq, serde::Deserialize, Unwrapped)]
+    #[unwrapped(default_from, derive(Debug, PartialEq))]
+    struct Post {
+        #[serde(default = "default_tags")]
+        tags: Option<Vec<String>>,
+        // A serde default of `None` still falls back to `Default::default()`
+        #[serde(rename = "headline", default = "no_title")]
+        title: Option<String>,
+        // The unwrapped default takes precedence over serde's
+        #[serde(default = "default_tags")]
+        #[unwrapped(default = vec!["draft".to_string()])]
+        labels: Option<Vec<String>>,
+        #[serde(default)]
+        views: Option<u32>,
+    }
+
+    let uw = PostUw::from(Post {
+        tags: None,
+        title: None,
+        labels: None,
+        views: None,
+    });
+    assert_eq!(
+        uw,
+        PostUw {
+            tags: vec!["new".to_string()],
+            title: String::new(),
+            labels: vec!["draft".to_string()],
+            views: 0,
+        }
+    );
+
+    // A present value is kept as is
+    let uw = PostUw::from(Post {
+        tags: Some(Vec::new()),
+        title: Some("Hello".to_string()),
+        labels: None,
+        views: Some(3),
+    });
+    assert!(uw.tags.is_empty());
+    assert_eq!(uw.title, "Hello");
+
+    // Deserializing the original and converting agree on missing keys
+    let post: Post = serde_json::from_str("{}").unwrap();
+    assert_eq!(PostUw::from(post).tags, vec!["new".to_string()]);
+}
+
+// ==================== Wrapped Tests ====================
+
+#[test]
+fn test_wrapped_simple_struct() {
+    #[derive(Debug, PartialEq, Wrapped)]
+    struct Simple {
+        field1: i32,
+        field2: String,
+        field3: Option<u64>,
+    }
+
+    let original = Simple {
+        field1: 10,
+        field2: "hello".to_string(),
+        field3: Some(100),
+    };
+
+    // Convert to wrapped - non-Option fields become Option
+    let wrapped = SimpleW::from(original);
+    assert_eq!(wrapped.field1, Some(10));
+    assert_eq!(wrapped.field2, Some("hello".to_string()));
+    assert_eq!(wrapped.field3, Some(100)); // Already Option, stays as-is
+
+    // Convert back
+    let converted_back: Simple = SimpleW::try_from(wrapped).unwrap();
+    assert_eq!(converted_back.field1, 10);
+    assert_eq!(converted_back.field2, "hello".to_string());
+    assert_eq!(converted_back.field3, Some(100));
+}
+
+#[test]
+fn test_wrapped_with_none_values() {
+    #[derive(Debug, PartialEq, Wrapped)]
+    struct WithOptionals {
+        required: i32,
+        optional: String,
+    }
+
+    let wrapped = WithOptionalsW {
+        required: Some(42),
+        optional: Some("value".to_string()),
+    };
+
+    // Conversion from wrapped requires all fields to be Some (no defaults!)
+    let converted: WithOptionals = WithOptionalsW::try_from(wrapped).unwrap();
+    assert_eq!(converted.required, 42);
+    assert_eq!(converted.optional, "value".to_string());
+}
+
+#[test]
+fn test_wrapped_try_from() {
+    #[derive(Debug, PartialEq, Wrapped)]
+    struct Config {
+        timeout: u64,
+        retries: i32,
+        name: String,
+    }
+
+    let wrapped_all_some = ConfigW {
+        timeout: Some(30),
+        retries: Some(3),
+        name: Some("test".to_string()),
+    };
+
+    // try_from should succeed when all fields are Some
+    let result = ConfigW::try_from(wrapped_all_some);
+    assert!(result.is_ok());
+    let config = result.unwrap();
+    assert_eq!(config.timeout, 30);
+    assert_eq!(config.retries, 3);
+    assert_eq!(config.name, "test".to_string());
+
+    let wrapped_missing = ConfigW {
+        timeout: Some(30),
+        retries: None,
+        name: Some("test".to_string()),
+    };
+
+    // try_from should fail when any field is None
+    let result = ConfigW::try_from(wrapped_missing);
+    assert!(result.is_err());
+    assert_eq!(result.unwrap_err().field_name, "retries");
+}
+
+#[test]
+fn test_wrapped_with_generics() {
+    #[derive(Clone, Debug, PartialEq, Wrapped)]
+    struct Generic<T: Clone + PartialEq + std::fmt::Debug + Default> {
+        value: T,
+        id: i32,
+    }
+
+    let original = Generic {
+        value: true,
+        id: 123,
+    };
+
+    let wrapped = GenericW::from(original);
+    assert_eq!(wrapped.value, Some(true));
+    assert_eq!(wrapped.id, Some(123));
+
+    let converted_back: Generic<bool> = GenericW::try_from(wrapped).unwrap();
+    assert_eq!(converted_back.value, true);
+    assert_eq!(converted_back.id, 123);
+}
+
+#[test]
+fn test_wrapped_trait() {
+    #[derive(Debug, PartialEq, Wrapped)]
+    struct MyStruct {
+        data: String,
+    }
+
+    fn check_wrapped<T: Wrapped<Wrapped = W>, W>(_: T) {}
+    check_wrapped(MyStruct {
+        data: "test".to_string(),
+    });
+}
+
+#[test]
+fn test_wrapped_skip_field() {
+    #[derive(Debug, PartialEq, Wrapped)]
+    #[wrapped(name = SkippedW)]
+    #[allow(dead_code)]
+    struct Skipped {
+        field_a: u32,
+        #[wrapped(skip)]
+        field_b: Option<String>,
+        field_c: bool,
+    }
+
+    // With skip, field_b is removed from the generated struct
+    // SkippedW only has field_a and field_c wrapped in Option
+    let wrapped = SkippedW {
+        field_a: Some(10),
+        field_c: Some(true),
+    };
+    assert_eq!(wrapped.field_a, Some(10));
+    assert_eq!(wrapped.field_c, Some(true));
+
+    // Verify we can construct with None values too
+    let wrapped2 = SkippedW {
+        field_a: Some(20),
+        field_c: None,
+    };
+    assert_eq!(wrapped2.field_a, Some(20));
+    assert_eq!(wrapped2.field_c, None);
+
+    // Note: From implementations are NOT generated when skip is used
+    // because we can't convert between structs with different field counts.
+}
+
+#[test]
+fn test_wrapped_with_custom_name() {
+    #[derive(Debug, PartialEq, Wrapped)]
+    #[wrapped(prefix = "A", name = UserWrapped, suffix = c)]
+    struct User0;
+
+    #[allow(dead_code)]
+    type Works0 = AUserWrappedc;
+
+    #[derive(Debug, PartialEq, Wrapped)]
+    #[wrapped(prefix = Bad)]
+    struct User1;
+
+    #[allow(dead_code)]
+    type Works1 = BadUser1;
+
+    #[derive(Debug, PartialEq, Wrapped)]
+    #[wrapped(suffix = "Something")]
+    struct User2;
+
+    #[allow(dead_code)]
+    type Works2 = User2Something;
+
+    #[derive(Debug, PartialEq, Wrapped)]
+    #[wrapped(prefix = Bad, suffix = Something)]
+    struct User3;
+
+    #[allow(dead_code)]
+    type Works3 = BadUser3Something;
+}
+
+#[test]
+fn test_wrapped_skip_field_into_original() {
+    #[derive(Debug, PartialEq, Wrapped)]
+    #[wrapped(name = ConfigW)]
+    struct Config {
+        timeout: u64,
+        retries: i32,
+        #[wrapped(skip)]
+        created_at: i64,
+        #[wrapped(skip)]
+        version: String,
+    }
+
+    // Create a wrapped struct (without skipped fields)
+    let wrapped = ConfigW {
+        timeout: Some(30),
+        retries: Some(3),
+    };
+
+    // Convert back to original using into_original, providing skipped fields
+    let original = wrapped
+        .into_original(1234567890, "v1.0".to_string())
+        .unwrap();
+
+    assert_eq!(original.timeout, 30);
+    assert_eq!(original.retries, 3);
+    assert_eq!(original.created_at, 1234567890);
+    assert_eq!(original.version, "v1.0".to_string());
+
+    // Test error case when wrapped field is None
+    let wrapped_none = ConfigW {
+        timeout: None,
+        retries: Some(5),
+    };
+
+    let result = wrapped_none.into_original(9999999999, "v2.0".to_string());
+    assert!(result.is_err());
+    assert_eq!(result.unwrap_err().field_name, "timeout");
+
+    // Test with manually constructed wrapped struct
+    let wrapped2 = ConfigW {
+        timeout: Some(60),
+        retries: Some(10),
+    };
+
+    // Convert back with skipped field values
+    let reconstructed = wrapped2
+        .into_original(2222222222, "v4.0".to_string())
+        .unwrap();
+    assert_eq!(reconstructed.timeout, 60);
+    assert_eq!(reconstructed.retries, 10);
+    assert_eq!(reconstructed.created_at, 2222222222);
+    assert_eq!(reconstructed.version, "v4.0".to_string());
 }
 
 #[test]
@@ -559,41 +3183,1001 @@ fn test_wrapped_skip_field_with_bon_builder_pattern() {
     struct UserForm {
         name: String,
         email: String,
-        note: Option<String>,
+        note: Option<String>,
+        #[wrapped(skip)]
+        created_at: i64,
+        #[wrapped(skip)]
+        id: u64,
+    }
+
+    let wrapped = UserFormW {
+        name: Some("Alice".to_string()),
+        email: Some("alice@example.com".to_string()),
+        note: Some("hello".to_string()),
+    };
+
+    let original = UserForm::builder()
+        .from_wrapped(wrapped)
+        .unwrap()
+        .created_at(1234567890)
+        .id(42)
+        .build();
+
+    assert_eq!(original.name, "Alice".to_string());
+    assert_eq!(original.email, "alice@example.com".to_string());
+    assert_eq!(original.note, Some("hello".to_string()));
+    assert_eq!(original.created_at, 1234567890);
+    assert_eq!(original.id, 42);
+
+    let wrapped_missing = UserFormW {
+        name: None,
+        email: Some("bob@example.com".to_string()),
+        note: None,
+    };
+
+    let err = UserForm::builder()
+        .from_wrapped(wrapped_missing)
+        .err()
+        .expect("expected error");
+    assert_eq!(err.field_name, "name");
+}
+
+#[test]
+fn test_wrapped_derive_form_derives_and_attrs() {
+    #[derive(Debug, PartialEq, Wrapped)]
+    #[wrapped(derive(Clone, Debug, PartialEq), attr(allow(dead_code)))]
+    struct Settings {
+        #[wrapped(attr(allow(unused)))]
+        theme: String,
+        volume: Option<u8>,
+    }
+
+    let wrapped = SettingsW::from(Settings {
+        theme: "dark".to_string(),
+        volume: None,
+    });
+
+    assert_eq!(wrapped.clone(), wrapped);
+    assert_eq!(
+        format!("{:?}", wrapped),
+        "SettingsW { theme: Some(\"dark\"), volume: None }"
+    );
+}
+
+#[test]
+fn test_wrapped_diff() {
+    #[derive(Clone, Debug, PartialEq, Wrapped)]
+    #[wrapped(diff)]
+    struct Account {
+        email: String,
+        age: u32,
+        nickname: Option<String>,
+    }
+
+    let old = Account {
+        email: "old@example.com".to_string(),
+        age: 30,
+        nickname: Some("neo".to_string()),
+    };
+    let new = Account {
+        email: "new@example.com".to_string(),
+        age: 30,
+        nickname: Some("trinity".to_string()),
+    };
+
+    let patch = AccountW::diff_wrapped(old.clone(), new);
+    assert_eq!(patch.email, Some("new@example.com".to_string()));
+    assert_eq!(patch.age, None);
+    assert_eq!(patch.nickname, Some("trinity".to_string()));
+
+    // Identical values produce an empty patch
+    let empty = AccountW::diff_wrapped(old.clone(), old);
+    assert_eq!(empty.email, None);
+    assert_eq!(empty.age, None);
+    assert_eq!(empty.nickname, None);
+}
+
+#[test]
+fn test_wrapped_transparent_newtype() {
+    #[derive(Debug, PartialEq)]
+    #[repr(transparent)]
+    struct UserId(u64);
+
+    #[derive(Debug, PartialEq, Wrapped)]
+    struct Session {
+        user: UserId,
+        parent: Option<UserId>,
+    }
+
+    let wrapped = SessionW::from(Session {
+        user: UserId(7),
+        parent: None,
+    });
+    assert_eq!(wrapped.user, Some(UserId(7)));
+
+    let original = SessionW::try_from(wrapped).unwrap();
+    assert_eq!(
+        original,
+        Session {
+            user: UserId(7),
+            parent: None,
+        }
+    );
+}
+
+#[test]
+fn test_wrapped_apply_all() {
+    #[derive(Debug, PartialEq, Wrapped)]
+    #[wrapped(apply_all)]
+    struct Profile {
+        name: String,
+        age: u32,
+        bio: Option<String>,
+    }
+
+    let base = Profile {
+        name: "Alice".to_string(),
+        age: 30,
+        bio: None,
+    };
+
+    let patches = vec![
+        ProfileW {
+            name: Some("Bob".to_string()),
+            age: None,
+            bio: Some("first".to_string()),
+        },
+        ProfileW {
+            name: Some("Carol".to_string()),
+            age: None,
+            bio: None,
+        },
+    ];
+
+    let result = ProfileW::apply_all(base, patches);
+    assert_eq!(
+        result,
+        Profile {
+            name: "Carol".to_string(), // Last patch wins
+            age: 30,                   // None in every patch keeps the base
+            bio: Some("first".to_string()),
+        }
+    );
+}
+
+#[test]
+fn test_wrapped_array_fields() {
+    #[derive(Debug, PartialEq, Wrapped)]
+    struct Buffer<const N: usize> {
+        bytes: [u8; N],
+    }
+
+    let w: BufferW<3> = Buffer { bytes: [1, 2, 3] }.into();
+    assert_eq!(w.bytes, Some([1, 2, 3]));
+    assert_eq!(BufferW::try_from(w).unwrap(), Buffer { bytes: [1, 2, 3] });
+}
+
+#[test]
+fn test_wrapped_skip_drops_unused_lifetime() {
+    #[derive(Debug, PartialEq, Wrapped)]
+    struct Request<'a> {
+        id: u32,
+        #[wrapped(skip)]
+        raw: &'a str,
+    }
+
+    let w: RequestW = RequestW { id: Some(1) };
+    let original = w.into_original("GET /").unwrap();
+    assert_eq!(
+        original,
+        Request {
+            id: 1,
+            raw: "GET /"
+        }
+    );
+}
+
+#[test]
+fn test_wrapped_changed_fields() {
+    #[derive(Debug, PartialEq, Wrapped)]
+    #[wrapped(changed_fields, attr(allow(dead_code)))]
+    #[allow(dead_code)]
+    struct Profile {
+        name: String,
+        age: u32,
+        bio: Option<String>,
+        #[wrapped(skip)]
+        id: u64,
+    }
+
+    let patch = ProfileW {
+        name: Some("Bob".to_string()),
+        age: None,
+        bio: Some("ignored".to_string()),
+    };
+    assert_eq!(patch.changed_fields(), vec!["name"]);
+
+    let empty = ProfileW {
+        name: None,
+        age: None,
+        bio: None,
+    };
+    assert!(empty.changed_fields().is_empty());
+}
+
+#[test]
+fn test_wrapped_iter_set() {
+    #[derive(Debug, PartialEq, Wrapped)]
+    #[wrapped(iter_set, attr(allow(dead_code)))]
+    #[allow(dead_code)]
+    struct Profile {
+        name: String,
+        age: u32,
+        bio: Option<String>,
+        #[wrapped(skip)]
+        id: u64,
+    }
+
+    let patch = ProfileW {
+        name: Some("Bob".to_string()),
+        age: Some(42),
+        bio: Some("ignored".to_string()),
+    };
+    let set: Vec<(&str, String)> = patch
+        .iter_set()
+        .map(|(name, value)| (name, format!("{value:?}")))
+        .collect();
+    assert_eq!(
+        set,
+        vec![("name", "\"Bob\"".to_string()), ("age", "42".to_string())]
+    );
+
+    let empty = ProfileW {
+        name: None,
+        age: None,
+        bio: None,
+    };
+    assert_eq!(empty.iter_set().count(), 0);
+}
+
+#[test]
+fn test_wrapped_forwards_serde_borrow() {
+    use serde::Deserialize;
+
+    #[derive(Debug, PartialEq, Deserialize, Wrapped)]
+    #[wrapped(derive(Debug, PartialEq, Deserialize))]
+    #[allow(dead_code)]
+    struct Message<'a> {
+        #[serde(borrow)]
+        body: &'a str,
+    }
+
+    let json = String::from(r#"{"body":"hello"}"#);
+    let w: MessageW = serde_json::from_str(&json).unwrap();
+    assert_eq!(w.body, Some("hello"));
+}
+
+#[test]
+fn test_wrapped_nullable() {
+    #[derive(Debug, PartialEq, Wrapped)]
+    #[wrapped(nullable("email", "bio"))]
+    struct Row {
+        id: i64,
+        email: String,
+        bio: Option<String>,
+        name: String,
+    }
+
+    let w: RowW = Row {
+        id: 1,
+        email: "a@example.com".to_string(),
+        bio: None,
+        name: "Alice".to_string(),
+    }
+    .into();
+
+    // Only `email` became an Option, `bio` already was one
+    assert_eq!(w.id, 1);
+    assert_eq!(w.email, Some("a@example.com".to_string()));
+    assert_eq!(w.bio, None);
+    assert_eq!(w.name, "Alice");
+
+    let row = RowW::try_from(w).unwrap();
+    assert_eq!(row.email, "a@example.com");
+}
+
+#[test]
+fn test_wrapped_nullable_bridge() {
+    #[derive(Debug, PartialEq, Unwrapped, Wrapped)]
+    #[unwrapped(bridge, derive(Debug, PartialEq))]
+    #[wrapped(nullable("email"))]
+    #[allow(dead_code)]
+    struct Row {
+        id: i64,
+        email: String,
+    }
+
+    let uw: RowUw = RowW {
+        id: 1,
+        email: Some("a@example.com".to_string()),
+    }
+    .try_into()
+    .unwrap();
+    assert_eq!(uw.id, 1);
+
+    let w: RowW = uw.into();
+    assert_eq!(w.id, 1);
+    assert_eq!(w.email, Some("a@example.com".to_string()));
+}
+
+#[test]
+fn test_wrapped_nested_mirror_fields() {
+    #[derive(Clone, Debug, PartialEq, Wrapped)]
+    #[wrapped(derive(Clone, Debug, PartialEq))]
+    struct Inner {
+        value: u8,
+    }
+
+    #[derive(Debug, PartialEq, Wrapped)]
+    #[wrapped(derive(Debug, PartialEq))]
+    struct Outer {
+        patch: <Inner as Wrapped>::Wrapped,
+        maybe: Option<InnerW>,
+    }
+
+    let patch = InnerW { value: Some(1) };
+    let w: OuterW = Outer {
+        patch: patch.clone(),
+        maybe: None,
+    }
+    .into();
+
+    // The nested mirror is wrapped once, not converted again
+    assert_eq!(w.patch, Some(patch.clone()));
+    assert_eq!(w.maybe, None);
+    assert_eq!(OuterW::try_from(w).unwrap().patch, patch);
+}
+
+#[test]
+fn test_wrapped_clearable() {
+    #[derive(Debug, PartialEq, Wrapped)]
+    #[wrapped(clearable, nullable("name"), derive(Debug, PartialEq))]
+    #[allow(dead_code)]
+    struct Profile {
+        name: String,
+        bio: Option<String>,
+        version: u32,
+    }
+
+    let mut patch = ProfileW {
+        name: Some("Bob".to_string()),
+        bio: Some("hello".to_string()),
+        version: 3,
+    };
+    patch.clear();
+
+    // Fields that are not Options keep their value
+    assert_eq!(
+        patch,
+        ProfileW {
+            name: None,
+            bio: None,
+            version: 3,
+        }
+    );
+}
+
+#[test]
+fn test_wrapped_method_name() {
+    #[derive(Debug, PartialEq, Wrapped)]
+    #[wrapped(method_name = "into_complete")]
+    struct Signup {
+        email: String,
+    }
+
+    let w = SignupW {
+        email: Some("a@example.com".to_string()),
+    };
+    assert_eq!(
+        SignupW::into_complete(w).unwrap(),
+        Signup {
+            email: "a@example.com".to_string()
+        }
+    );
+}
+
+#[test]
+fn test_wrapped_completable() {
+    #[derive(Debug, PartialEq, Wrapped)]
+    #[wrapped(inherit_vis)]
+    struct Form {
+        name: String,
+        age: u32,
+    }
+
+    fn finish<T: Completable>(partial: T) -> Result<T::Complete, UnwrappedError> {
+        partial.complete()
+    }
+
+    let form = finish(FormW {
+        name: Some("ann".to_string()),
+        age: Some(30),
+    })
+    .unwrap();
+    assert_eq!(
+        form,
+        Form {
+            name: "ann".to_string(),
+            age: 30
+        }
+    );
+
+    let err = finish(FormW {
+        name: Some("ann".to_string()),
+        age: None,
+    })
+    .unwrap_err();
+    assert_eq!(err.field_name, "age");
+}
+
+#[test]
+fn test_wrapped_cmp_against_original() {
+    #[derive(Debug, PartialEq, Wrapped)]
+    #[wrapped(cmp)]
+    struct Account {
+        id: u32,
+        email: String,
+        nickname: Option<String>,
         #[wrapped(skip)]
-        created_at: i64,
+        #[allow(dead_code)]
+        secret: String,
+    }
+
+    let account = Account {
+        id: 1,
+        email: "a@example.com".to_string(),
+        nickname: None,
+        secret: "hunter2".to_string(),
+    };
+
+    // `None` matches anything, so an empty patch matches
+    assert!(
+        account
+            == AccountW {
+                id: None,
+                email: None,
+                nickname: None,
+            }
+    );
+    assert!(
+        account
+            == AccountW {
+                id: Some(1),
+                email: None,
+                nickname: None,
+            }
+    );
+    assert!(
+        account
+            != AccountW {
+                id: Some(2),
+                email: None,
+                nickname: None,
+            }
+    );
+
+    // `nickname` was already optional, so it is compared directly
+    assert!(
+        account
+            != AccountW {
+                id: None,
+                email: None,
+                nickname: Some("al".to_string()),
+            }
+    );
+}
+
+#[test]
+fn test_wrapped_field_enum() {
+    #[derive(Debug, PartialEq, Wrapped)]
+    #[wrapped(derive(Debug, Default, PartialEq), field_enum)]
+    struct UserProfile {
+        display_name: String,
+        age: u32,
+        bio: Option<String>,
+    }
+
+    let mut patch = UserProfileW::default();
+    patch.set(UserProfileWField::DisplayName("ann".to_string()));
+    patch.set(UserProfileWField::Age(30));
+    // Already optional, so the variant carries the `Option`
+    patch.set(UserProfileWField::Bio(None));
+
+    assert_eq!(
+        patch,
+        UserProfileW {
+            display_name: Some("ann".to_string()),
+            age: Some(30),
+            bio: None,
+        }
+    );
+}
+
+#[test]
+fn test_wrapped_defaults_fill() {
+    #[derive(Debug, PartialEq, Wrapped)]
+    #[wrapped(derive(Default), defaults_fill)]
+    struct ServerConfig {
+        host: String,
+        #[wrapped(default = 8080)]
+        port: u16,
+        #[wrapped(default = "info".to_string())]
+        log_level: String,
+        #[wrapped(skip, default = Vec::new())]
+        plugins: Vec<String>,
+    }
+
+    let config = ServerConfigW::from_partial_with_defaults(ServerConfigW {
+        host: Some("localhost".to_string()),
+        port: None,
+        log_level: Some("debug".to_string()),
+    })
+    .unwrap();
+    assert_eq!(
+        config,
+        ServerConfig {
+            host: "localhost".to_string(),
+            port: 8080,
+            log_level: "debug".to_string(),
+            plugins: Vec::new(),
+        }
+    );
+
+    // `host` has no default, so it must be present
+    let err = ServerConfigW::from_partial_with_defaults(ServerConfigW::default()).unwrap_err();
+    assert_eq!(err.field_name, "host");
+}
+
+#[test]
+fn test_wrapped_from_wrapped_setter_selection() {
+    #[derive(bon::Builder, Debug, PartialEq, Wrapped)]
+    struct Profile {
+        name: String,
+        nickname: Option<String>,
+        #[builder(required)]
+        avatar: Option<String>,
+        #[builder(default = 30)]
+        timeout: u32,
         #[wrapped(skip)]
         id: u64,
     }
 
-    let wrapped = UserFormW {
-        name: Some("Alice".to_string()),
-        email: Some("alice@example.com".to_string()),
-        note: Some("hello".to_string()),
-    };
+    let original = Profile::builder()
+        .from_wrapped(ProfileW {
+            name: Some("ann".to_string()),
+            nickname: None,
+            avatar: None,
+            timeout: None,
+        })
+        .unwrap()
+        .id(7)
+        .build();
+    assert_eq!(
+        original,
+        Profile {
+            name: "ann".to_string(),
+            nickname: None,
+            avatar: None,
+            timeout: 30,
+            id: 7,
+        }
+    );
 
-    let original = UserForm::builder()
-        .from_wrapped(wrapped)
+    let original = Profile::builder()
+        .from_wrapped(ProfileW {
+            name: Some("bob".to_string()),
+            nickname: Some("b".to_string()),
+            avatar: Some("b.png".to_string()),
+            timeout: Some(5),
+        })
         .unwrap()
-        .created_at(1234567890)
-        .id(42)
+        .id(8)
         .build();
+    assert_eq!(original.nickname.as_deref(), Some("b"));
+    assert_eq!(original.avatar.as_deref(), Some("b.png"));
+    assert_eq!(original.timeout, 5);
 
-    assert_eq!(original.name, "Alice".to_string());
-    assert_eq!(original.email, "alice@example.com".to_string());
-    assert_eq!(original.note, Some("hello".to_string()));
-    assert_eq!(original.created_at, 1234567890);
-    assert_eq!(original.id, 42);
+    // `name` has neither an `Option` nor a builder default, so it stays required
+    let err = Profile::builder()
+        .from_wrapped(ProfileW {
+            name: None,
+            nickname: None,
+            avatar: None,
+            timeout: None,
+        })
+        .err()
+        .unwrap();
+    assert_eq!(err.field_name, "name");
+}
 
-    let wrapped_missing = UserFormW {
+#[test]
+fn test_wrapped_enum_variants() {
+    #[derive(Debug, Clone, PartialEq, Wrapped)]
+    #[wrapped(derive(Debug, Clone, PartialEq))]
+    enum Command {
+        Resize { width: u32, height: Option<u32> },
+        Say(String),
+        Quit,
+    }
+
+    let w = CommandW::from(Command::Resize {
+        width: 10,
+        height: None,
+    });
+    assert_eq!(
+        w,
+        CommandW::Resize {
+            width: Some(10),
+            height: None
+        }
+    );
+    assert_eq!(
+        CommandW::try_from(w).unwrap(),
+        Command::Resize {
+            width: 10,
+            height: None
+        }
+    );
+
+    let w = CommandW::from(Command::Say("hi".to_string()));
+    assert_eq!(w, CommandW::Say(Some("hi".to_string())));
+    assert_eq!(
+        CommandW::try_from(w).unwrap(),
+        Command::Say("hi".to_string())
+    );
+    assert_eq!(CommandW::try_from(CommandW::Quit).unwrap(), Command::Quit);
+
+    let err = CommandW::try_from(CommandW::Resize {
+        width: None,
+        height: Some(5),
+    })
+    .unwrap_err();
+    assert_eq!(err.field_name, "Resize.width");
+    let err = CommandW::try_from(CommandW::Say(None)).unwrap_err();
+    assert_eq!(err.field_name, "Say.0");
+}
+
+#[test]
+fn test_bridge_reconcile_applies_patch() {
+    #[derive(Debug, PartialEq, Unwrapped, Wrapped)]
+    #[unwrapped(bridge, derive(Debug, PartialEq))]
+    struct Profile {
+        name: Option<String>,
+        bio: Option<String>,
+        age: u32,
+        #[unwrapped(skip)]
+        visits: u64,
+    }
+
+    let uw = ProfileUw {
+        name: "ann".to_string(),
+        bio: "hi".to_string(),
+        age: 30,
+    };
+    let patch = ProfileW {
         name: None,
-        email: Some("bob@example.com".to_string()),
-        note: None,
+        bio: Some("hello".to_string()),
+        age: Some(31),
+        visits: Some(9),
     };
 
-    let err = UserForm::builder()
-        .from_wrapped(wrapped_missing)
-        .err()
-        .expect("expected error");
-    assert_eq!(err.field_name, "name");
+    // `name` is missing from the patch and `visits` is skipped by `Unwrapped`, so both are ignored
+    assert_eq!(
+        uw.reconcile(patch).unwrap(),
+        ProfileUw {
+            name: "ann".to_string(),
+            bio: "hello".to_string(),
+            age: 31,
+        }
+    );
+}
+
+#[test]
+fn test_wrapped_try_from_trait() {
+    #[derive(Debug, PartialEq, Wrapped)]
+    #[wrapped(derive(Debug, PartialEq))]
+    struct Order {
+        id: u32,
+        note: Option<String>,
+    }
+
+    fn submit(patch: OrderW) -> Result<Order, UnwrappedError> {
+        let order: Order = patch.try_into()?;
+        Ok(order)
+    }
+
+    assert_eq!(
+        submit(OrderW {
+            id: Some(3),
+            note: None
+        })
+        .unwrap(),
+        Order { id: 3, note: None }
+    );
+    let err = submit(OrderW {
+        id: None,
+        note: Some("rush".to_string()),
+    })
+    .unwrap_err();
+    assert_eq!(err.field_name, "id");
+}
+
+#[test]
+fn test_wrapped_rename_field() {
+    #[derive(Debug, Clone, PartialEq, Wrapped)]
+    #[wrapped(derive(Debug, Clone, PartialEq), changed_fields)]
+    struct ApiUser {
+        #[wrapped(rename = "id")]
+        user_id: u64,
+        nickname: Option<String>,
+    }
+
+    let original = ApiUser {
+        user_id: 7,
+        nickname: None,
+    };
+    let w = ApiUserW::from(original.clone());
+    assert_eq!(
+        w,
+        ApiUserW {
+            id: Some(7),
+            nickname: None,
+        }
+    );
+    assert_eq!(w.changed_fields(), ["user_id"]);
+    assert_eq!(ApiUserW::try_from(w).unwrap(), original);
+
+    let err = ApiUserW::try_from(ApiUserW {
+        id: None,
+        nickname: None,
+    })
+    .unwrap_err();
+    assert_eq!(err.field_name, "user_id");
+}
+
+#[test]
+fn test_wrapped_cfg_fields() {
+    #[derive(Debug, Clone, PartialEq, Wrapped)]
+    #[wrapped(
+        derive(Debug, Clone, PartialEq),
+        apply_all,
+        changed_fields,
+        clearable,
+        cmp,
+        diff,
+        field_enum
+    )]
+    struct Settings {
+        name: String,
+        #[cfg(not(test))]
+        extra: u32,
+        #[cfg(test)]
+        level: u8,
+    }
+
+    let original = Settings {
+        name: "dev".to_string(),
+        level: 2,
+    };
+    let mut w = SettingsW::from(original.clone());
+    assert_eq!(
+        w,
+        SettingsW {
+            name: Some("dev".to_string()),
+            level: Some(2),
+        }
+    );
+    assert!(original == w);
+    assert_eq!(w.changed_fields(), ["name", "level"]);
+    w.set(SettingsWField::Level(3));
+    assert_eq!(SettingsW::try_from(w.clone()).unwrap().level, 3);
+    assert_eq!(SettingsW::apply_all(original.clone(), [w.clone()]).level, 3);
+    w.clear();
+    assert_eq!(SettingsW::diff_wrapped(original.clone(), original), w);
+    assert_eq!(<Settings as Wrapped>::FIELD_COUNT, 2);
+}
+
+#[test]
+fn test_wrapped_merge_into() {
+    #[derive(Debug, Clone, PartialEq, Wrapped)]
+    struct Entity {
+        name: String,
+        age: u32,
+        nickname: Option<String>,
+        #[wrapped(merge_replace)]
+        bio: Option<String>,
+        #[wrapped(skip)]
+        id: u64,
+    }
+
+    let mut entity = Entity {
+        name: "Alice".to_string(),
+        age: 30,
+        nickname: Some("Al".to_string()),
+        bio: Some("Hello".to_string()),
+        id: 7,
+    };
+
+    // A sparse patch only touches the fields it sets
+    EntityW {
+        name: None,
+        age: Some(31),
+        nickname: None,
+        bio: Some("Hi".to_string()),
+    }
+    .merge_into(&mut entity);
+    assert_eq!(
+        entity,
+        Entity {
+            name: "Alice".to_string(),
+            age: 31,
+            nickname: Some("Al".to_string()),
+            bio: Some("Hi".to_string()),
+            id: 7,
+        }
+    );
+
+    // `merge_replace` lets `None` clear the field, other `Option` fields keep their value
+    EntityW {
+        name: None,
+        age: None,
+        nickname: None,
+        bio: None,
+    }
+    .merge_into(&mut entity);
+    assert_eq!(entity.age, 31);
+    assert_eq!(entity.nickname, Some("Al".to_string()));
+    assert_eq!(entity.bio, None);
+}
+
+mod shadowed_names {
+    // Local items named after everything the derives reference, the generated code must not
+    // pick any of them up
+    #[allow(dead_code)]
+    pub struct Unwrapped;
+    #[allow(dead_code)]
+    pub struct Wrapped;
+    #[allow(dead_code)]
+    pub struct UnwrappedError;
+    #[allow(dead_code)]
+    pub struct Completable;
+    #[allow(dead_code)]
+    pub struct From;
+    #[allow(dead_code)]
+    pub struct TryFrom;
+    #[allow(dead_code)]
+    pub type Result<T> = core::result::Result<T, ()>;
+    #[allow(dead_code)]
+    pub struct Vec;
+    #[allow(dead_code)]
+    pub struct Option;
+
+    #[derive(Debug, PartialEq, unwrapped::Unwrapped, unwrapped::Wrapped)]
+    #[unwrapped(view, expect, analyze, missing_fields, collect_errors)]
+    #[wrapped(changed_fields)]
+    pub struct Config {
+        pub host: core::option::Option<String>,
+        pub port: u16,
+    }
+}
+
+#[test]
+fn test_generated_paths_ignore_shadowed_names() {
+    use shadowed_names::{Config, ConfigUw, ConfigW};
+
+    let config = Config {
+        host: Some("localhost".to_string()),
+        port: 80,
+    };
+    let view = config.view().unwrap();
+    assert_eq!((view.host, view.port), ("localhost", &80));
+    assert!(ConfigUw::missing_fields(&config).is_empty());
+    assert_eq!(ConfigUw::analyze(&config).len(), 2);
+    let uw = ConfigUw::expect_unwrapped(config);
+    assert_eq!(uw.host, "localhost");
+
+    let (partial, errors) = ConfigUw::try_from_partial(Config {
+        host: None,
+        port: 80,
+    });
+    assert!(partial.is_none());
+    assert_eq!(errors.len(), 1);
+
+    let w = ConfigW::from(Config::from(uw));
+    assert_eq!(w.changed_fields(), ["port"]);
+    assert_eq!(
+        Config::try_from(w).unwrap(),
+        Config {
+            host: Some("localhost".to_string()),
+            port: 80,
+        }
+    );
+}
+
+#[test]
+fn test_wrapped_tristate() {
+    #[derive(Clone, Debug, PartialEq, Wrapped)]
+    #[wrapped(tristate, diff)]
+    struct Profile {
+        name: String,
+        bio: Option<String>,
+        website: Option<String>,
+    }
+
+    let mut profile = Profile {
+        name: "Ada".to_string(),
+        bio: Some("Hi".to_string()),
+        website: Some("ada.dev".to_string()),
+    };
+
+    // Already-`Option` fields are nested, every state of the original is `Some`
+    let w = ProfileW::from(profile.clone());
+    assert_eq!(w.bio, Some(Some("Hi".to_string())));
+
+    // Absent leaves the field, `Some(None)` clears it and `Some(Some(_))` sets it
+    ProfileW {
+        name: None,
+        bio: Some(None),
+        website: None,
+    }
+    .merge_into(&mut profile);
+    assert_eq!(profile.bio, None);
+    assert_eq!(profile.website, Some("ada.dev".to_string()));
+
+    ProfileW {
+        name: None,
+        bio: Some(Some("Hello".to_string())),
+        website: None,
+    }
+    .merge_into(&mut profile);
+    assert_eq!(profile.bio, Some("Hello".to_string()));
+
+    // Converting back reads an absent field as `None`
+    let original = Profile::try_from(ProfileW {
+        name: Some("Ada".to_string()),
+        bio: None,
+        website: Some(None),
+    })
+    .unwrap();
+    assert_eq!(
+        original,
+        Profile {
+            name: "Ada".to_string(),
+            bio: None,
+            website: None,
+        }
+    );
+
+    // A field changed to `None` is now part of the diff
+    let patch = ProfileW::diff_wrapped(
+        profile.clone(),
+        Profile {
+            website: None,
+            ..profile.clone()
+        },
+    );
+    assert_eq!(patch.website, Some(None));
+    assert_eq!(patch.bio, None);
+
+    #[derive(Debug, PartialEq, Wrapped)]
+    #[wrapped(tristate, derive(Debug, PartialEq))]
+    enum Change {
+        Note(Option<String>),
+    }
+
+    assert_eq!(ChangeW::from(Change::Note(None)), ChangeW::Note(Some(None)));
+    assert_eq!(
+        Change::try_from(ChangeW::Note(None)).unwrap(),
+        Change::Note(None)
+    );
 }
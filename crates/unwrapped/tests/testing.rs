@@ -0,0 +1,31 @@
+#![cfg(feature = "testing")]
+
+use unwrapped::Unwrapped;
+
+#[derive(Debug, PartialEq, Unwrapped)]
+#[unwrapped(derive(Debug, PartialEq), sample)]
+struct Customer {
+    #[unwrapped(sample = 42)]
+    id: Option<u64>,
+    #[unwrapped(sample = "\"Ann\".to_string()")]
+    name: Option<String>,
+    #[unwrapped(default = 3)]
+    tier: Option<u8>,
+    tags: Vec<String>,
+    #[unwrapped(skip)]
+    secret: String,
+}
+
+#[test]
+fn sample_fills_every_field() {
+    assert_eq!(
+        CustomerUw::sample(),
+        CustomerUw {
+            id: 42,
+            name: "Ann".to_string(),
+            tier: 3,
+            tags: Vec::new(),
+        }
+    );
+    assert_eq!(CustomerUw::sample(), CustomerUw::sample());
+}
@@ -0,0 +1,41 @@
+#![cfg(feature = "zeroize")]
+
+use unwrapped::Unwrapped;
+use zeroize::Zeroize as _;
+
+#[derive(Debug, PartialEq)]
+struct Handle(u32);
+
+#[derive(Debug, PartialEq, Unwrapped)]
+#[unwrapped(derive(Debug, PartialEq), zeroize)]
+struct Credentials {
+    password: Option<String>,
+    pin: Option<Vec<u8>>,
+    #[unwrapped(zeroize_skip)]
+    username: Option<String>,
+    // `Handle` does not implement `Zeroize`, so it must be skipped
+    #[unwrapped(zeroize_skip)]
+    handle: Handle,
+}
+
+#[test]
+fn test_zeroize_wipes_unwrapped_secrets() {
+    let mut uw = CredentialsUw::try_from(Credentials {
+        password: Some("hunter2".to_string()),
+        pin: Some(vec![1, 2, 3, 4]),
+        username: Some("alice".to_string()),
+        handle: Handle(7),
+    })
+    .unwrap();
+
+    uw.zeroize();
+    assert_eq!(
+        uw,
+        CredentialsUw {
+            password: String::new(),
+            pin: Vec::new(),
+            username: "alice".to_string(),
+            handle: Handle(7),
+        }
+    );
+}